@@ -0,0 +1,130 @@
+//! Derive for EIP-712 `encodeType`, `typeHash` and `hashStruct`
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Field, Ident, LitStr};
+
+use crate::{find_str_value, is_ignored, is_rust_only, named_fields, reject_flatten};
+
+/// Generate `Eip712Field` and `Eip712` impls for a struct, `rust_only` fields are not members
+pub(crate) fn impl_eip712(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let type_name = find_str_value(&input.attrs, "rename")
+        .unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+    let camel_case = match find_str_value(&input.attrs, "rename_all") {
+        Some(rule) if rule.value() == "camelCase" => true,
+        Some(rule) => {
+            return Err(syn::Error::new_spanned(
+                rule,
+                "unsupported rename_all rule, expected \"camelCase\"",
+            ))
+        }
+        None => false,
+    };
+
+    let mut members = Vec::<&Field>::new();
+    for f in named_fields(input)?.named.iter() {
+        if is_rust_only(&f.attrs) {
+            continue;
+        }
+        if is_ignored(&f.attrs) {
+            return Err(syn::Error::new_spanned(
+                f,
+                "ignored fields have no value to hash, use `rust_only` to exclude the field from the EIP-712 type",
+            ));
+        }
+        reject_flatten(f, "Eip712")?;
+        members.push(f);
+    }
+
+    let mut generics = input.generics.clone();
+    let type_params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: _ethabi_static::Eip712Field));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let member_types = members.iter().enumerate().map(|(idx, f)| {
+        let f_type = &f.ty;
+        let separator = (idx > 0).then(|| quote! { f.write_str(",")?; });
+        let write_type = match find_str_value(&f.attrs, "abi_type") {
+            Some(abi_type) => quote! { f.write_str(#abi_type)?; },
+            None => quote! { <#f_type as _ethabi_static::Eip712Field>::write_eip712_type(f)?; },
+        };
+        let f_ident = f.ident.as_ref().expect("named field");
+        let member_name = match find_str_value(&f.attrs, "rename") {
+            Some(rename) => rename.value(),
+            None if camel_case => to_camel_case(&f_ident.to_string()),
+            None => f_ident.to_string(),
+        };
+        let member_name = format!(" {}", member_name);
+        quote! { #separator #write_type f.write_str(#member_name)?; }
+    });
+    let referenced_types = members.iter().map(|f| {
+        let f_type = &f.ty;
+        quote! { <#f_type as _ethabi_static::Eip712Field>::eip712_struct_types(types); }
+    });
+    let encode_members = members.iter().map(|f| {
+        let f_name = &f.ident;
+        quote! {
+            out.extend_from_slice(&_ethabi_static::Eip712Field::eip712_encode_value(&self.#f_name));
+        }
+    });
+    let type_open = format!("{}(", type_name.value());
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::Eip712Field for #name #ty_generics #where_clause {
+                fn write_eip712_type<__W: ::core::fmt::Write>(f: &mut __W) -> ::core::fmt::Result {
+                    f.write_str(#type_name)
+                }
+                fn eip712_struct_types(
+                    types: &mut _ethabi_static::__private::BTreeMap<&'static str, _ethabi_static::__private::String>,
+                ) {
+                    if types.contains_key(#type_name) {
+                        return;
+                    }
+                    let mut encoded = _ethabi_static::__private::String::new();
+                    <Self as _ethabi_static::Eip712>::write_eip712_struct_type(&mut encoded)
+                        .expect("String is infallible");
+                    types.insert(#type_name, encoded);
+                    #(#referenced_types)*
+                }
+                fn eip712_encode_value(&self) -> [u8; 32] {
+                    _ethabi_static::Eip712::hash_struct(self)
+                }
+            }
+            impl #impl_generics _ethabi_static::Eip712 for #name #ty_generics #where_clause {
+                const NAME: &'static str = #type_name;
+                fn write_eip712_struct_type<__W: ::core::fmt::Write>(f: &mut __W) -> ::core::fmt::Result {
+                    f.write_str(#type_open)?;
+                    #(#member_types)*
+                    f.write_str(")")
+                }
+                fn eip712_encode_data(&self, out: &mut _ethabi_static::__private::Vec<u8>) {
+                    #(#encode_members)*
+                }
+            }
+        };
+    })
+}
+
+/// `verifying_contract` => `verifyingContract`
+fn to_camel_case(snake: &str) -> String {
+    let mut camel = String::with_capacity(snake.len());
+    let mut upper = false;
+    for c in snake.chars() {
+        if c == '_' {
+            upper = !camel.is_empty();
+        } else if upper {
+            camel.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
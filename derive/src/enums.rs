@@ -0,0 +1,79 @@
+//! Derive for Solidity `enum`s i.e. fieldless Rust enums encoded as `uint8`
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DataEnum, DeriveInput, Fields, Meta, NestedMeta};
+
+/// Generate `DecodeStatic` and `AbiType` impls for a fieldless enum, a discriminant without a
+/// variant is an `Err`
+pub(crate) fn impl_decode_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+    check_repr(input)?;
+    let name = &input.ident;
+
+    let mut discriminants = Vec::<TokenStream>::with_capacity(data.variants.len());
+    let mut arms = Vec::<TokenStream>::with_capacity(data.variants.len());
+    for (idx, variant) in data.variants.iter().enumerate() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                &variant.fields,
+                "DecodeStatic can only be derived for enums without fields, Solidity enums are uint8",
+            ));
+        }
+        let v_name = &variant.ident;
+        let discriminant = format_ident!("__D{}", idx);
+        let out_of_range = format!("`{}::{}` does not fit in a uint8", name, v_name);
+        discriminants.push(quote! {
+            const #discriminant: u8 = {
+                let d = #name::#v_name as i128;
+                assert!(d >= 0 && d <= u8::MAX as i128, #out_of_range);
+                d as u8
+            };
+        });
+        arms.push(quote! { #discriminant => Ok(#name::#v_name), });
+    }
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            fn from_discriminant(d: u8) -> Result<#name, ()> {
+                #(#discriminants)*
+                match d {
+                    #(#arms)*
+                    _ => Err(()),
+                }
+            }
+            impl<'a> _ethabi_static::DecodeStatic<'a> for #name {
+                // a value above 255 isn't a variant either, strict or not
+                fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+                    from_discriminant(<u8 as _ethabi_static::DecodeStatic>::decode_static_strict(buf, offset)?)
+                }
+            }
+            impl _ethabi_static::AbiType for #name {
+                fn write_abi_type<__W: ::core::fmt::Write>(f: &mut __W) -> ::core::fmt::Result {
+                    f.write_str("uint8")
+                }
+            }
+        };
+    })
+}
+
+/// `Err` if the enum has an integer `repr` other than `u8`
+fn check_repr(input: &DeriveInput) -> syn::Result<()> {
+    const WIDER: [&str; 11] = [
+        "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("repr")) {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested.iter() {
+                if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                    if WIDER.iter().any(|int| path.is_ident(int)) {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "Solidity enums are uint8, use #[repr(u8)]",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -3,43 +3,133 @@
 //! Intended for high speed decoding, not feature completeness
 //! Trades binary size for performance vs. ethabi
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{parse::Parse, spanned::Spanned, Attribute, Data, DeriveInput, Fields, Meta, NestedMeta};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Attribute, Data, DeriveInput, Field,
+    Fields, FieldsNamed, GenericArgument, Ident, Lit, LitStr, Meta, NestedMeta, Path,
+    PathArguments, Token, Type, WherePredicate,
+};
 
+mod eip712;
+mod enums;
+mod owned;
+mod packed;
+mod tokens;
+mod transparent;
+
+/// Derive `DecodeStatic` and `AbiType` for a struct with named fields, or for a fieldless enum
+/// decoded from a `uint8` (a Solidity `enum`) where an unknown discriminant is an `Err`
+///
+/// Container attributes:
+/// - `#[ethabi(transparent)]` a struct with one field (named or not) is encoded exactly as the
+///   field, e.g. a Solidity user defined value type, rather than as a one element tuple
+///
+/// Field attributes:
+/// - `#[ethabi(ignore)]` (alias `skip`) ignore the field's ABI value, use `Default::default()` instead
+/// - `#[ethabi(rust_only)]` Rust side field that occupies no ABI slot, use `Default::default()`
+/// - `#[ethabi(default = "expr")]` use `expr` for an ignored or `rust_only` field (implies `ignore`)
+/// - `#[ethabi(with = "path::decode_fn")]` decode the field with
+///   `fn(buf: &'a [u8], offset: usize) -> Result<T, ()>` given the field's head offset
+/// - `#[ethabi(abi_type = "uint112")]` Solidity type of the field in generated signatures, ignored
+///   and `with` fields need one for the struct to implement `AbiType`
+/// - `#[ethabi(flatten)]` inline a static derived struct's fields as consecutive head words of the
+///   parent rather than a nested tuple
+/// - `#[ethabi(optional)]` a trailing `Option<T>` field that is `None` if the struct's head ends
+///   before it i.e. the buffer is too short or a tail starts there, for older contract versions
 #[proc_macro_derive(DecodeStatic, attributes(ethabi))]
 pub fn decode_static_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input: DeriveInput = match syn::parse(input) {
+    let input = match parse_input(input) {
         Ok(input) => input,
         Err(e) => return e.to_compile_error().into(),
     };
+    if let Data::Enum(ref data) = input.data {
+        return enums::impl_decode_enum(&input, data)
+            .unwrap_or_else(|e| e.to_compile_error())
+            .into();
+    }
+    if has_flag(&input.attrs, "transparent") {
+        return transparent::impl_transparent(&input)
+            .unwrap_or_else(|e| e.to_compile_error())
+            .into();
+    }
 
+    let fields = match named_fields(&input) {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let name = &input.ident;
-    let steps = decode_steps(input.data);
+    let (steps, strict_steps) = match (decode_steps(fields, false), decode_steps(fields, true)) {
+        (Ok(steps), Ok(strict_steps)) => (steps, strict_steps),
+        (Err(e), _) | (_, Err(e)) => return e.to_compile_error().into(),
+    };
+    let abi_type_impl = abi_type_impl(&input, fields);
 
     // TODO: do this with one quote...
     // support 1 lifetime and 1 generic only
     let lifetime = input.generics.lifetimes().next();
     let generic = input.generics.type_params().next();
 
-    match (lifetime, generic) {
+    let buf_lifetime = match lifetime {
+        Some(def) => def.lifetime.clone(),
+        None => parse_quote!('a),
+    };
+    let into_items = match decode_into_steps(fields) {
+        Ok(Some(into_steps)) => quote! {
+            fn decode_static_into(&mut self, buf: &#buf_lifetime [u8], offset: usize) -> Result<(), ()> {
+                #into_steps
+            }
+            fn decode_into(&mut self, buf: &#buf_lifetime [u8]) -> Result<(), ()> {
+                self.decode_static_into(buf, 0)
+            }
+        },
+        Ok(None) => TokenStream::new(),
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let head = struct_head(fields);
+    let (head_size, dynamic) = (head.tokens(), head.dynamic_tokens());
+    // a struct with dynamic fields is a dynamic tuple but `decode` reads it in place, as the
+    // function params or return values
+    let struct_items = quote! {
+        const DYNAMIC: bool = #dynamic;
+        const HEAD_SIZE: usize = if Self::DYNAMIC { 32 } else { #head_size };
+        fn decode(buf: &#buf_lifetime [u8]) -> Result<Self, ()> {
+            Self::decode_static(buf, 0)
+        }
+        fn decode_strict(buf: &#buf_lifetime [u8]) -> Result<Self, ()> {
+            Self::decode_static_strict(buf, 0)
+        }
+        fn decode_static_strict(buf: &#buf_lifetime [u8], offset: usize) -> Result<Self, ()> {
+            Ok(Self::decode_tail_strict(buf, offset)?.0)
+        }
+        #into_items
+    };
+    let decode_impl = match (lifetime, generic) {
         (Some(lifetime), Some(generic)) => {
             quote! {
                 impl<#lifetime, #generic> DecodeStatic<#lifetime> for #name<#lifetime, #generic>
                 where
                     #generic: DecodeStatic<#lifetime>
                 {
+                    #struct_items
                     fn decode_static(buf: &#lifetime [u8], offset: usize) -> Result<Self, ()> {
                         #steps
                     }
+                    fn decode_tail_strict(buf: &#lifetime [u8], offset: usize) -> Result<(Self, usize), ()> {
+                        #strict_steps
+                    }
                 }
             }
         }
         (Some(lifetime), None) => {
             quote! {
                 impl<#lifetime> DecodeStatic<#lifetime> for #name<#lifetime> {
+                    #struct_items
                     fn decode_static(buf: &#lifetime [u8], offset: usize) -> Result<Self, ()> {
                         #steps
                     }
+                    fn decode_tail_strict(buf: &#lifetime [u8], offset: usize) -> Result<(Self, usize), ()> {
+                        #strict_steps
+                    }
                 }
             }
         }
@@ -49,107 +139,605 @@ pub fn decode_static_derive(input: proc_macro::TokenStream) -> proc_macro::Token
                 where
                     #generic: DecodeStatic<'a>
                 {
+                    #struct_items
                     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
                         #steps
                     }
+                    fn decode_tail_strict(buf: &'a [u8], offset: usize) -> Result<(Self, usize), ()> {
+                        #strict_steps
+                    }
                 }
             }
         }
         _ => {
             quote! {
                 impl<'a> DecodeStatic<'a> for #name {
+                    #struct_items
                     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
                         #steps
                     }
+                    fn decode_tail_strict(buf: &'a [u8], offset: usize) -> Result<(Self, usize), ()> {
+                        #strict_steps
+                    }
                 }
             }
         }
+    };
+
+    let exact_impl = exact_impl(&input, &head, fields);
+
+    quote! {
+        #decode_impl
+        #abi_type_impl
+        #exact_impl
     }
     .into()
 }
 
-fn decode_steps(data: Data) -> TokenStream {
-    match data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields_named) => {
-                let len = fields_named.named.len();
-                let mut head_stmts = Vec::<TokenStream>::with_capacity(len);
-                let mut tail_stmts = Vec::<TokenStream>::with_capacity(len);
-
-                for (idx, f) in fields_named.named.iter().enumerate() {
-                    let f_name = f.ident.clone().unwrap();
-                    let f_type = &f.ty;
-                    let offset = 32_usize * idx;
-                    let type_string = f_type.to_token_stream().to_string().replace(" ", "");
-
-                    let is_list = type_string.starts_with("Vec");
-                    let field_is_dynamic: bool = is_list || type_string.starts_with("BytesZcp");
-
-                    if should_skip(&f.attrs) {
-                        tail_stmts.push(quote! {
-                            #f_name: Default::default(),
-                        });
-                        continue;
-                    }
+/// Generate `ENCODED_SIZE`, `decode_exact` and `decode_exact_slice` for a struct without type
+/// params, `Vec`, bytes or `optional` fields. If another field is dynamic using them fails to build
+fn exact_impl(input: &DeriveInput, head: &HeadOffset, fields: &FieldsNamed) -> TokenStream {
+    let has_optional = fields.named.iter().any(|f| is_optional(&f.attrs));
+    if head.dynamic || has_optional || input.generics.type_params().next().is_some() {
+        return TokenStream::new();
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lifetime = match input.generics.lifetimes().next() {
+        Some(def) => def.lifetime.clone(),
+        None => parse_quote!('_),
+    };
+    // array lengths can't use the struct's lifetime
+    let lifetimes: Vec<&syn::Lifetime> = input.generics.lifetimes().map(|l| &l.lifetime).collect();
+    let encoded_size = owned::replace_lifetimes(head.tokens(), &lifetimes);
+
+    quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Size of the ABI encoding, every field is static
+                pub const ENCODED_SIZE: usize = {
+                    assert!(
+                        !<Self as _ethabi_static::DecodeStatic>::DYNAMIC,
+                        "only static structs have an ENCODED_SIZE"
+                    );
+                    #encoded_size
+                };
+
+                /// Decode from exactly `ENCODED_SIZE` bytes
+                // inline so `assert_static` is only instantiated, and checked, where it's used
+                #[inline]
+                pub fn decode_exact(buf: &#lifetime [u8; #encoded_size]) -> Result<Self, ()> {
+                    _ethabi_static::__private::assert_static::<Self>();
+                    <Self as _ethabi_static::DecodeStatic>::decode_static(buf, 0)
+                }
+
+                /// Decode like `decode` but `Err` on trailing bytes e.g. the return data of a
+                /// different function
+                #[inline]
+                pub fn decode_exact_slice(buf: &#lifetime [u8]) -> Result<Self, ()> {
+                    _ethabi_static::__private::assert_static::<Self>();
+                    Self::decode_exact(buf.try_into().map_err(|_| ())?)
+                }
+            }
+        };
+    }
+}
 
-                    if !field_is_dynamic {
-                        head_stmts.push(quote! {
-                            let #f_name = <#f_type>::decode_static(buf, #offset)?;
-                        });
-                        tail_stmts.push(quote! {
-                            #f_name,
-                        });
-                        continue;
+/// Derive `IntoOwned` and an owned companion struct `<Name>Owned` for a zero-copy struct, an enum
+/// without lifetimes is its own owned type
+#[proc_macro_derive(IntoOwned, attributes(ethabi))]
+pub fn into_owned_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse_input(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    owned::impl_into_owned(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive `IntoTokens` and `IntoToken` for a struct with named fields (requires the `ethabi` feature)
+#[proc_macro_derive(IntoTokens, attributes(ethabi))]
+pub fn into_tokens_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse_input(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    tokens::impl_into_tokens(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive `FromTokens` and `FromToken` for a struct with named fields (requires the `ethabi` feature)
+#[proc_macro_derive(FromTokens, attributes(ethabi))]
+pub fn from_tokens_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse_input(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    tokens::impl_from_tokens(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive `EncodePacked` for a struct with named fields (requires the `alloc` feature)
+///
+/// `#[ethabi(abi_type = "uint24")]` packs a uint field into the given width, at most the Rust type's
+#[proc_macro_derive(EncodePacked, attributes(ethabi))]
+pub fn encode_packed_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse_input(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    packed::impl_encode_packed(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive `DecodePacked` for a struct with named fields
+///
+/// `#[ethabi(abi_type = "uint24")]` reads a uint field from the given width, at most the Rust type's
+#[proc_macro_derive(DecodePacked, attributes(ethabi))]
+pub fn decode_packed_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse_input(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    packed::impl_decode_packed(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive `Eip712` (`encodeType`, `typeHash` and `hashStruct`) for a struct with named fields
+/// (requires the `alloc` feature)
+///
+/// Member types are the fields' ABI types, `#[ethabi(abi_type = "uint112")]` overrides one.
+/// `#[ethabi(rename = "...")]` on the struct or a field sets its EIP-712 name and
+/// `#[ethabi(rename_all = "camelCase")]` on the struct renames all fields e.g. `chain_id => chainId`
+#[proc_macro_derive(Eip712, attributes(ethabi))]
+pub fn eip712_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match parse_input(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    eip712::impl_eip712(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate an `AbiType` impl giving the struct's Solidity tuple type e.g. `(address,uint256)`
+///
+/// The impl only applies if every field's type is an `AbiType`, ignored and `with` fields need an
+/// `abi_type` as their Rust type says nothing about the ABI value. Otherwise nothing is generated
+fn abi_type_impl(input: &DeriveInput, fields: &FieldsNamed) -> TokenStream {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    let abi_fields = fields.named.iter().filter(|f| !is_rust_only(&f.attrs));
+    let mut field_types = Vec::<TokenStream>::with_capacity(fields.named.len());
+    for (idx, f) in abi_fields.enumerate() {
+        let separator = (idx > 0).then(|| quote! { f.write_str(",")?; });
+        let f_type = value_type(f);
+        let write_type = match find_str_value(&f.attrs, "abi_type") {
+            Some(abi_type) => quote! { f.write_str(#abi_type)?; },
+            None if is_ignored(&f.attrs) || find_str_value(&f.attrs, "with").is_some() => {
+                return TokenStream::new();
+            }
+            None if is_flattened(&f.attrs) => {
+                where_clause.predicates.push(abi_type_bound(f_type));
+                quote! { <#f_type as _ethabi_static::AbiType>::write_abi_fields(f)?; }
+            }
+            None => {
+                where_clause.predicates.push(abi_type_bound(f_type));
+                quote! { <#f_type as _ethabi_static::AbiType>::write_abi_type(f)?; }
+            }
+        };
+        field_types.push(quote! { #separator #write_type });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::AbiType for #name #ty_generics #where_clause {
+                fn write_abi_type<__W: ::core::fmt::Write>(f: &mut __W) -> ::core::fmt::Result {
+                    f.write_str("(")?;
+                    Self::write_abi_fields(f)?;
+                    f.write_str(")")
+                }
+                fn write_abi_fields<__W: ::core::fmt::Write>(f: &mut __W) -> ::core::fmt::Result {
+                    #(#field_types)*
+                    Ok(())
+                }
+            }
+        };
+    }
+}
+
+/// `ty: AbiType`, higher ranked so an unsatisfied bound on a concrete type leaves the impl
+/// unimplemented rather than failing to build
+fn abi_type_bound(ty: &Type) -> WherePredicate {
+    parse_quote!(for<'__abi> #ty: _ethabi_static::AbiType)
+}
+
+/// The named fields of a struct, or an error spanning the unsupported item
+fn named_fields(input: &DeriveInput) -> syn::Result<&FieldsNamed> {
+    match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields_named) => Ok(fields_named),
+            Fields::Unnamed(ref fields) => Err(syn::Error::new_spanned(
+                fields,
+                "DecodeStatic cannot be derived for tuple structs, use named fields instead",
+            )),
+            Fields::Unit => Err(syn::Error::new(
+                input.ident.span(),
+                "DecodeStatic cannot be derived for unit structs, add named fields",
+            )),
+        },
+        Data::Enum(ref data) => Err(syn::Error::new(
+            data.enum_token.span(),
+            "DecodeStatic cannot be derived for enums, use a struct with named fields instead",
+        )),
+        Data::Union(ref data) => Err(syn::Error::new(
+            data.union_token.span(),
+            "DecodeStatic cannot be derived for unions, use a struct with named fields instead",
+        )),
+    }
+}
+
+/// Generate the body of `decode_static` or, if `strict`, `decode_tail_strict`
+///
+/// Fields are read from their heads in place, following offsets relative to the struct's start for
+/// dynamic fields. Strict decoding validates each field and requires dynamic fields' offsets to be
+/// word aligned, in field order and not overlap the previous field's tail, giving the end of the
+/// last tail
+fn decode_steps(fields: &FieldsNamed, strict: bool) -> syn::Result<TokenStream> {
+    let len = fields.named.len();
+    let mut head_stmts = Vec::<TokenStream>::with_capacity(len);
+    let mut tail_stmts = Vec::<TokenStream>::with_capacity(len);
+
+    // offset of the next field's head from the struct's start
+    let mut head_offset = HeadOffset::default();
+    let decode_fn = if strict {
+        quote! { decode_static_strict }
+    } else {
+        quote! { decode_static }
+    };
+
+    // optional fields are present if their head ends before the struct's head does, which is at the
+    // end of the buffer or where the first tail starts
+    let has_optional = fields.named.iter().any(|f| is_optional(&f.attrs));
+    let mut present_stmts = Vec::<TokenStream>::new();
+    // the struct's head size given the present fields, innermost is all of them
+    let mut absent_heads = Vec::<(Ident, TokenStream)>::new();
+    let mut trailing = false;
+
+    for f in fields.named.iter() {
+        let f_name = f.ident.clone().unwrap();
+        let optional = is_optional(&f.attrs);
+        let f_type = if optional {
+            option_inner_type(f)?
+        } else {
+            &f.ty
+        };
+        let rel_offset = head_offset.tokens();
+        if is_rust_only(&f.attrs) {
+            let value = default_value(f)?;
+            tail_stmts.push(quote! {
+                #f_name: #value,
+            });
+            continue;
+        }
+        if trailing && !optional {
+            return Err(syn::Error::new_spanned(
+                f,
+                "fields after an `optional` field must be `optional` too",
+            ));
+        }
+        trailing |= optional;
+        head_offset.add_field(f);
+
+        let present = format_ident!("__{}_present", f_name);
+        if has_optional {
+            let dynamic = quote! { <#f_type as _ethabi_static::DecodeStatic>::DYNAMIC };
+            let narrow = quote! {
+                if #dynamic {
+                    __head_end = _ethabi_static::__private::narrow_head_end(buf, offset + #rel_offset, __head_end);
+                }
+            };
+            if optional {
+                let head_end = head_offset.tokens();
+                present_stmts.push(quote! {
+                    let #present = #head_end <= __head_end;
+                    if #present {
+                        #narrow
                     }
+                });
+                absent_heads.push((present.clone(), rel_offset.clone()));
+            } else if !is_ignored(&f.attrs) && find_str_value(&f.attrs, "with").is_none() {
+                present_stmts.push(narrow);
+            }
+        }
 
-                    // if dynamic we read the head then decode tail after
-                    head_stmts.push(
-                        quote! {
-                            let #f_name = ((unsafe { *buf.get_unchecked(#offset + 30) } as usize) << 8) + (unsafe { *buf.get_unchecked(#offset + 31) } as usize);
-                        }
-                    );
+        if is_ignored(&f.attrs) {
+            let value = default_value(f)?;
+            tail_stmts.push(quote! {
+                #f_name: #value,
+            });
+            continue;
+        }
+        // bound to a prefixed local so a field named e.g. `offset` doesn't shadow the parameter
+        let local = format_ident!("__field_{}", f_name);
+        tail_stmts.push(quote! {
+            #f_name: #local,
+        });
 
-                    if is_list {
-                        let mut ts = f_type.clone().into_token_stream().into_iter();
-                        let dynamic_inner =
-                            if let Some(proc_macro2::TokenTree::Ident(list_type)) = ts.nth(2) {
-                                if list_type == "Vec" {
-                                    unimplemented!("nested arrays unsupported");
-                                }
-                                list_type.to_string() == "BytesZcp"
-                            } else {
-                                false
-                            };
-
-                        tail_stmts.push(quote! {
-                            #f_name: <_ethabi_static::Array<_, #dynamic_inner>>::decode_static(buf, #f_name)?.0,
-                        });
+        let value = if is_flattened(&f.attrs) {
+            // a static struct is decoded in place so its fields are the parent's head words
+            quote! {{
+                const { assert!(!<#f_type>::DYNAMIC, "only static structs can be flattened") };
+                <#f_type>::#decode_fn(buf, offset + #rel_offset)?
+            }}
+        } else if let Some(with) = with_path(f)? {
+            quote! { #with(buf, offset + #rel_offset)? }
+        } else {
+            if strict {
+                // the next tail may not overlap this one
+                quote! {
+                    if <#f_type>::DYNAMIC {
+                        let tail_offset = __tails.next(buf, offset + #rel_offset)?;
+                        let (value, end) = <#f_type>::decode_tail_strict(buf, offset + tail_offset)?;
+                        __tails.end(end - offset);
+                        value
                     } else {
-                        tail_stmts.push(quote! {
-                            #f_name: <#f_type>::decode_static(buf, #f_name)?,
-                        });
+                        <#f_type>::#decode_fn(buf, offset + #rel_offset)?
                     }
                 }
+            } else {
+                quote! { <#f_type>::decode_from_head(buf, offset, offset + #rel_offset)? }
+            }
+        };
+        if optional {
+            head_stmts.push(quote! {
+                let #local = if #present { Some(#value) } else { None };
+            });
+        } else {
+            head_stmts.push(quote! {
+                let #local = #value;
+            });
+        }
+    }
 
-                quote! {
-                    extern crate ethabi_static as _ethabi_static;
-                    #(#head_stmts)*
-                    Ok(Self {
-                        #(#tail_stmts)*
-                    })
-                }
+    let mut prelude = Vec::<TokenStream>::new();
+    if has_optional {
+        prelude.push(quote! {
+            #[allow(unused_mut)]
+            let mut __head_end = buf.len().saturating_sub(offset);
+            #(#present_stmts)*
+        });
+    }
+    if strict {
+        // the head ends at the first absent field
+        let mut head_size = struct_head(fields).tokens();
+        for (present, rel_offset) in absent_heads.iter().rev() {
+            head_size = quote! { if #present { #head_size } else { #rel_offset } };
+        }
+        prelude.push(quote! {
+            #[allow(unused_mut, unused_variables)]
+            let mut __tails = _ethabi_static::__private::TailCursor::new(#head_size);
+        });
+    }
+    head_stmts.splice(0..0, prelude);
+
+    let value = quote! {
+        Self {
+            #(#tail_stmts)*
+        }
+    };
+    let result = if strict {
+        quote! { (#value, offset + __tails.tail_end()) }
+    } else {
+        value
+    };
+    Ok(quote! {
+        extern crate ethabi_static as _ethabi_static;
+        #(#head_stmts)*
+        Ok(#result)
+    })
+}
+
+/// Generate the body of `decode_static_into` decoding each field into itself, `None` if the struct
+/// has `optional` fields
+///
+/// Like `decode`, ignored and `rust_only` fields are reset to their default
+fn decode_into_steps(fields: &FieldsNamed) -> syn::Result<Option<TokenStream>> {
+    if fields.named.iter().any(|f| is_optional(&f.attrs)) {
+        return Ok(None);
+    }
+    let mut head_offset = HeadOffset::default();
+    let mut stmts = Vec::<TokenStream>::with_capacity(fields.named.len());
+    for f in fields.named.iter() {
+        let f_name = &f.ident;
+        let rel_offset = head_offset.tokens();
+        if !is_rust_only(&f.attrs) {
+            head_offset.add_field(f);
+        }
+        let stmt = if is_rust_only(&f.attrs) || is_ignored(&f.attrs) {
+            let value = default_value(f)?;
+            quote! { self.#f_name = #value; }
+        } else if let Some(with) = with_path(f)? {
+            quote! { self.#f_name = #with(buf, offset + #rel_offset)?; }
+        } else if is_flattened(&f.attrs) {
+            quote! {
+                _ethabi_static::DecodeStatic::decode_static_into(&mut self.#f_name, buf, offset + #rel_offset)?;
             }
-            _ => unimplemented!(),
-        },
-        _ => unimplemented!(),
+        } else {
+            quote! {
+                _ethabi_static::DecodeStatic::decode_from_head_into(&mut self.#f_name, buf, offset, offset + #rel_offset)?;
+            }
+        };
+        stmts.push(stmt);
+    }
+    Ok(Some(quote! {
+        extern crate ethabi_static as _ethabi_static;
+        #(#stmts)*
+        Ok(())
+    }))
+}
+
+/// The struct's fields' heads, their total size and whether any field is dynamic
+fn struct_head(fields: &FieldsNamed) -> HeadOffset {
+    let mut head = HeadOffset::default();
+    for f in fields.named.iter().filter(|f| !is_rust_only(&f.attrs)) {
+        head.add_field(f);
+    }
+    head
+}
+
+/// A running head offset, whole words plus the `HEAD_SIZE` of fields whose size isn't known until
+/// type checking
+#[derive(Default)]
+struct HeadOffset {
+    words: usize,
+    sized: Vec<syn::Type>,
+    /// whether a field's type is known to be dynamic by name, see `is_known_dynamic`
+    dynamic: bool,
+}
+
+impl HeadOffset {
+    /// Move past `f`'s head, `with` and ignored fields take one word
+    fn add_field(&mut self, f: &Field) {
+        let ty = value_type(f);
+        // an ignored field's type needn't be `DecodeStatic`
+        self.dynamic |= is_known_dynamic(ty);
+        if is_ignored(&f.attrs) || find_str_value(&f.attrs, "with").is_some() {
+            self.words += 1;
+        } else {
+            self.sized.push(ty.clone());
+        }
+    }
+
+    /// The offset so far in bytes
+    fn tokens(&self) -> TokenStream {
+        let bytes = 32 * self.words;
+        let sized = &self.sized;
+        quote! { (#bytes #(+ <#sized>::HEAD_SIZE)*) }
+    }
+
+    /// Whether any field so far is dynamic
+    fn dynamic_tokens(&self) -> TokenStream {
+        let dynamic = self.dynamic;
+        let sized = &self.sized;
+        quote! { (#dynamic #(|| <#sized>::DYNAMIC)*) }
+    }
+}
+
+/// Whether the type is one of this crate's array, bytes or string types i.e. `Vec`, `SmallVec`,
+/// `ArrayVec`, `BytesZcp` or `StrZcp`
+///
+/// Only a hint before type checking, head sizes and offsets come from `DYNAMIC` and `HEAD_SIZE`
+fn is_known_dynamic(ty: &Type) -> bool {
+    let type_string = ty.to_token_stream().to_string().replace(" ", "");
+    let path = type_string.split('<').next().unwrap_or_default();
+    let ident = path.rsplit("::").next().unwrap_or_default();
+    matches!(
+        ident,
+        "Vec" | "SmallVec" | "ArrayVec" | "BytesZcp" | "StrZcp"
+    )
+}
+
+/// The decode fn of a `#[ethabi(with = "path::decode_fn")]` field
+fn with_path(f: &Field) -> syn::Result<Option<Path>> {
+    let with = match find_str_value(&f.attrs, "with") {
+        Some(with) => with,
+        None => return Ok(None),
+    };
+    with.parse::<Path>().map(Some).map_err(|e| {
+        syn::Error::new(
+            e.span(),
+            "`with` expects a path to a decode fn e.g. \"my_mod::decode_foo\"",
+        )
+    })
+}
+
+/// Whether the field's ABI value is not decoded i.e. `ignore`, `skip` or `default = "..."`
+fn is_ignored(attrs: &[Attribute]) -> bool {
+    should_skip(attrs) || find_str_value(attrs, "default").is_some()
+}
+
+/// The value of a field that isn't decoded, `#[ethabi(default = "expr")]` or `Default::default()`
+fn default_value(f: &Field) -> syn::Result<TokenStream> {
+    match find_str_value(&f.attrs, "default") {
+        Some(expr) => expr.parse::<TokenStream>(),
+        None => Ok(quote! { Default::default() }),
     }
 }
 
-/// Look for a `#[ethabi(skip)]` in the given attributes.
+/// Look for a `#[ethabi(ignore)]` (or its alias `#[ethabi(skip)]`) in the given attributes.
 fn should_skip(attrs: &[Attribute]) -> bool {
+    has_flag(attrs, "ignore") || has_flag(attrs, "skip")
+}
+
+/// Look for a `#[ethabi(rust_only)]` in the given attributes.
+fn is_rust_only(attrs: &[Attribute]) -> bool {
+    has_flag(attrs, "rust_only")
+}
+
+/// Look for a `#[ethabi(flatten)]` in the given attributes.
+fn is_flattened(attrs: &[Attribute]) -> bool {
+    has_flag(attrs, "flatten")
+}
+
+/// Look for a `#[ethabi(optional)]` in the given attributes.
+fn is_optional(attrs: &[Attribute]) -> bool {
+    has_flag(attrs, "optional")
+}
+
+/// The type of the field's ABI value i.e. `T` of an `optional` field's `Option<T>`
+fn value_type(f: &Field) -> &Type {
+    match is_optional(&f.attrs) {
+        true => option_inner_type(f).unwrap_or(&f.ty),
+        false => &f.ty,
+    }
+}
+
+/// `Option<T>` => `T` for an `optional` field
+fn option_inner_type(f: &Field) -> syn::Result<&Type> {
+    if let Type::Path(ref path) = f.ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(ref args) = segment.arguments {
+                if let (true, Some(GenericArgument::Type(inner))) =
+                    (segment.ident == "Option", args.args.first())
+                {
+                    return Ok(inner);
+                }
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(
+        &f.ty,
+        "`optional` fields must be an `Option<T>`",
+    ))
+}
+
+/// `Err` for a `flatten` field in a derive that doesn't support it
+fn reject_flatten(f: &Field, derive: &str) -> syn::Result<()> {
+    if is_flattened(&f.attrs) {
+        return Err(syn::Error::new_spanned(
+            f,
+            format!("`flatten` is not supported by {}", derive),
+        ));
+    }
+    Ok(())
+}
+
+/// Look for a `#[ethabi(<name>)]` in the given attributes.
+fn has_flag(attrs: &[Attribute], name: &str) -> bool {
     find_meta_item(attrs.iter(), |meta| {
         if let NestedMeta::Meta(Meta::Path(ref path)) = meta {
-            if path.is_ident("skip") {
+            if path.is_ident(name) {
                 return Some(path.span());
             }
         }
@@ -159,16 +747,97 @@ fn should_skip(attrs: &[Attribute]) -> bool {
     .is_some()
 }
 
-fn find_meta_item<'a, F, R, I, M>(mut itr: I, mut pred: F) -> Option<R>
+/// Look for a `#[ethabi(<name> = "...")]` in the given attributes.
+fn find_str_value(attrs: &[Attribute], name: &str) -> Option<LitStr> {
+    find_meta_item(attrs.iter(), |meta| {
+        if let NestedMeta::Meta(Meta::NameValue(ref nv)) = meta {
+            if nv.path.is_ident(name) {
+                if let Lit::Str(ref s) = nv.lit {
+                    return Some(s.clone());
+                }
+            }
+        }
+
+        None
+    })
+}
+
+/// Parse a derive's input, `Err` for a malformed or unknown `#[ethabi(...)]` attribute
+fn parse_input(input: proc_macro::TokenStream) -> syn::Result<DeriveInput> {
+    let input: DeriveInput = syn::parse(input)?;
+    check_attrs(&input.attrs)?;
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => {
+            for variant in data.variants.iter() {
+                check_attrs(&variant.attrs)?;
+            }
+            data.variants.iter().flat_map(|v| v.fields.iter()).collect()
+        }
+        Data::Union(ref data) => data.fields.named.iter().collect(),
+    };
+    for f in fields {
+        check_attrs(&f.attrs)?;
+    }
+    Ok(input)
+}
+
+/// `Err` unless each `#[ethabi(...)]` is a list of the attributes the derives know, shared by all
+/// of them as they share the `ethabi` attribute
+fn check_attrs(attrs: &[Attribute]) -> syn::Result<()> {
+    const FLAGS: [&str; 6] = [
+        "transparent",
+        "ignore",
+        "skip",
+        "rust_only",
+        "flatten",
+        "optional",
+    ];
+    const STR_VALUES: [&str; 5] = ["default", "with", "abi_type", "rename", "rename_all"];
+    const LISTS: [&str; 1] = ["owned_derive"];
+    for attr in attrs.iter().filter(|a| a.path.is_ident("ethabi")) {
+        let items = attr
+            .parse_args_with(Punctuated::<NestedMeta, Token![,]>::parse_terminated)
+            .map_err(|e| {
+                syn::Error::new_spanned(attr, format!("malformed `ethabi` attribute: {}", e))
+            })?;
+        for item in items.iter() {
+            let known = match item {
+                NestedMeta::Meta(Meta::Path(path)) => FLAGS.iter().any(|k| path.is_ident(k)),
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    matches!(nv.lit, Lit::Str(_)) && STR_VALUES.iter().any(|k| nv.path.is_ident(k))
+                }
+                NestedMeta::Meta(Meta::List(list)) => LISTS.iter().any(|k| list.path.is_ident(k)),
+                NestedMeta::Lit(_) => false,
+            };
+            if !known {
+                return Err(syn::Error::new_spanned(
+                    item,
+                    "unknown `ethabi` attribute, expected one of `transparent`, `ignore`, `skip`, \
+                     `rust_only`, `flatten`, `optional`, `default = \"..\"`, `with = \"..\"`, \
+                     `abi_type = \"..\"`, `rename = \"..\"`, `rename_all = \"..\"` or \
+                     `owned_derive(..)`",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn find_meta_item<'a, F, R, I>(mut itr: I, mut pred: F) -> Option<R>
 where
-    F: FnMut(M) -> Option<R> + Clone,
+    F: FnMut(&NestedMeta) -> Option<R>,
     I: Iterator<Item = &'a Attribute>,
-    M: Parse,
 {
     itr.find_map(|attr| {
         attr.path
             .is_ident("ethabi")
-            .then(|| pred(attr.parse_args().ok()?))
+            .then(|| {
+                attr.parse_args_with(Punctuated::<NestedMeta, Token![,]>::parse_terminated)
+                    .ok()?
+                    .iter()
+                    .find_map(&mut pred)
+            })
             .flatten()
     })
 }
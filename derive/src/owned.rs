@@ -0,0 +1,169 @@
+//! Derive for an owned companion struct of a zero-copy struct
+use proc_macro2::{Punct, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, Data, DeriveInput, Lifetime, Meta, NestedMeta, Path};
+
+use crate::{find_meta_item, is_rust_only, named_fields};
+
+/// Generate `<Name>Owned` with every borrowed field converted to its `IntoOwned::Owned` type
+/// and an `IntoOwned` impl mapping into it
+///
+/// `rust_only` fields are moved as is, derives on the owned struct default to `Debug, PartialEq`
+/// and may be set with `#[ethabi(owned_derive(Debug, Clone))]`
+///
+/// A fieldless enum borrows nothing and is its own owned type
+pub(crate) fn impl_into_owned(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    if matches!(input.data, Data::Enum(_)) && input.generics.params.is_empty() {
+        return Ok(quote! {
+            const _: () = {
+                extern crate ethabi_static as _ethabi_static;
+                impl _ethabi_static::IntoOwned for #name {
+                    type Owned = Self;
+                    fn into_owned(self) -> Self::Owned {
+                        self
+                    }
+                }
+            };
+        });
+    }
+    let vis = &input.vis;
+    let owned_name = format_ident!("{}Owned", name);
+    let fields = named_fields(input)?;
+
+    let lifetimes: Vec<&Lifetime> = input.generics.lifetimes().map(|l| &l.lifetime).collect();
+    let type_params: Vec<_> = input.generics.type_params().map(|p| &p.ident).collect();
+    let owned_derives: Vec<Path> = find_owned_derives(input)
+        .unwrap_or_else(|| vec![parse_quote!(Debug), parse_quote!(PartialEq)])
+        .into_iter()
+        .map(|path| {
+            // the prelude only has these as derive macros, qualify so they work as bounds
+            if path.is_ident("Debug") {
+                parse_quote!(::core::fmt::Debug)
+            } else if path.is_ident("Hash") {
+                parse_quote!(::core::hash::Hash)
+            } else {
+                path
+            }
+        })
+        .collect();
+
+    let mut owned_bounds: Vec<TokenStream> = type_params
+        .iter()
+        .map(|p| quote! { #p: ::ethabi_static::IntoOwned })
+        .collect();
+    let mut owned_fields = Vec::<TokenStream>::with_capacity(fields.named.len());
+    let mut conversions = Vec::<TokenStream>::with_capacity(fields.named.len());
+    for f in fields.named.iter() {
+        let f_name = &f.ident;
+        let f_vis = &f.vis;
+        let f_type = &f.ty;
+        if is_rust_only(&f.attrs) {
+            owned_fields.push(quote! { #f_vis #f_name: #f_type, });
+            conversions.push(quote! { #f_name: self.#f_name, });
+            continue;
+        }
+        // the owned type must not mention the borrowed lifetimes
+        let static_type = replace_lifetimes(f_type.to_token_stream(), &lifetimes);
+        let owned_type = quote! { <#static_type as ::ethabi_static::IntoOwned>::Owned };
+        // derives don't bound projections of type params so do it on the struct
+        if !type_params.is_empty() {
+            owned_bounds.extend(owned_derives.iter().map(|d| quote! { #owned_type: #d }));
+        }
+        owned_fields.push(quote! {
+            #f_vis #f_name: #owned_type,
+        });
+        conversions.push(quote! {
+            #f_name: _ethabi_static::IntoOwned::into_owned(self.#f_name),
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut impl_bounds: Vec<TokenStream> = where_clause
+        .map(|w| w.predicates.iter().map(|p| p.to_token_stream()).collect())
+        .unwrap_or_default();
+    impl_bounds.extend(owned_bounds.iter().cloned());
+
+    Ok(quote! {
+        #[derive(#(#owned_derives),*)]
+        #vis struct #owned_name<#(#type_params),*>
+        where
+            #(#owned_bounds,)*
+        {
+            #(#owned_fields)*
+        }
+
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::IntoOwned for #name #ty_generics
+            where
+                #(#impl_bounds,)*
+            {
+                type Owned = #owned_name<#(#type_params),*>;
+                fn into_owned(self) -> Self::Owned {
+                    #owned_name {
+                        #(#conversions)*
+                    }
+                }
+            }
+        };
+    })
+}
+
+/// Look for a `#[ethabi(owned_derive(...))]` in the given attributes.
+fn find_owned_derives(input: &DeriveInput) -> Option<Vec<Path>> {
+    find_meta_item(input.attrs.iter(), |meta| {
+        if let NestedMeta::Meta(Meta::List(ref list)) = meta {
+            if list.path.is_ident("owned_derive") {
+                return Some(
+                    list.nested
+                        .iter()
+                        .filter_map(|nested| match nested {
+                            NestedMeta::Meta(Meta::Path(path)) => Some(path.clone()),
+                            _ => None,
+                        })
+                        .collect(),
+                );
+            }
+        }
+
+        None
+    })
+}
+
+/// Replace any of the given lifetimes in `ts` with `'static`
+pub(crate) fn replace_lifetimes(ts: TokenStream, lifetimes: &[&Lifetime]) -> TokenStream {
+    let mut out = TokenStream::new();
+    let mut tokens = ts.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(ref p) if p.as_char() == '\'' && p.spacing() == Spacing::Joint => {
+                match tokens.peek() {
+                    Some(TokenTree::Ident(ident))
+                        if lifetimes.iter().any(|l| l.ident == *ident) =>
+                    {
+                        let span = ident.span();
+                        tokens.next();
+                        let mut apostrophe = Punct::new('\'', Spacing::Joint);
+                        apostrophe.set_span(p.span());
+                        out.extend([
+                            TokenTree::Punct(apostrophe),
+                            TokenTree::Ident(proc_macro2::Ident::new("static", span)),
+                        ]);
+                    }
+                    _ => out.extend([token]),
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_lifetimes(group.stream(), lifetimes),
+                );
+                replaced.set_span(group.span());
+                out.extend([TokenTree::Group(replaced)]);
+            }
+            other => out.extend([other]),
+        }
+    }
+    out
+}
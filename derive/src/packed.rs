@@ -0,0 +1,183 @@
+//! Derives for `abi.encodePacked` encoding and decoding
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_quote, DeriveInput, Field, GenericParam, Lifetime, LifetimeDef, Type};
+
+use crate::{default_value, find_str_value, is_ignored, is_rust_only, named_fields};
+
+/// Generate an `EncodePacked` impl concatenating the fields, `rust_only` fields are dropped
+pub(crate) fn impl_encode_packed(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let abi_fields: Vec<&Field> = named_fields(input)?
+        .named
+        .iter()
+        .filter(|f| !is_rust_only(&f.attrs))
+        .collect();
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    let mut encode_fields = Vec::<TokenStream>::with_capacity(abi_fields.len());
+    for f in abi_fields.iter() {
+        let f_name = &f.ident;
+        let f_type = &f.ty;
+        match packed_uint_bytes(f)? {
+            Some(bytes) => {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#f_type: _ethabi_static::__private::PackedUint));
+                encode_fields.push(quote! {
+                    _ethabi_static::__private::PackedUint::encode_packed_uint(&self.#f_name, #bytes, out);
+                });
+            }
+            None => {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#f_type: _ethabi_static::EncodePacked));
+                encode_fields.push(quote! {
+                    _ethabi_static::EncodePacked::encode_packed_to(&self.#f_name, out);
+                });
+            }
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::EncodePacked for #name #ty_generics #where_clause {
+                fn encode_packed_to(&self, out: &mut _ethabi_static::__private::Vec<u8>) {
+                    #(#encode_fields)*
+                }
+            }
+        };
+    })
+}
+
+/// Generate a `DecodePacked` impl reading the fields in order
+///
+/// Ignored fields are decoded then replaced by their default, `rust_only` fields consume no input
+pub(crate) fn impl_decode_packed(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let fields = named_fields(input)?;
+
+    // borrow for the struct's own lifetime if it has one
+    let mut generics = input.generics.clone();
+    let lifetime: Lifetime = match generics.lifetimes().next() {
+        Some(def) => def.lifetime.clone(),
+        None => {
+            let lifetime: Lifetime = parse_quote!('__p);
+            generics.params.insert(
+                0,
+                GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+            );
+            lifetime
+        }
+    };
+
+    let mut decode_stmts = Vec::<TokenStream>::with_capacity(fields.named.len());
+    let mut field_values = Vec::<TokenStream>::with_capacity(fields.named.len());
+    let where_clause = generics.make_where_clause();
+    for f in fields.named.iter() {
+        let f_name = &f.ident;
+        if is_rust_only(&f.attrs) {
+            let value = default_value(f)?;
+            field_values.push(quote! { #f_name: #value, });
+            continue;
+        }
+
+        let f_type = &f.ty;
+        let decode = match packed_uint_bytes(f)? {
+            Some(bytes) => {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#f_type: _ethabi_static::__private::PackedUint));
+                quote! {
+                    <#f_type as _ethabi_static::__private::PackedUint>::decode_packed_uint(buf, #bytes)?
+                }
+            }
+            None => {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#f_type: _ethabi_static::DecodePacked<#lifetime>));
+                quote! {
+                    <#f_type as _ethabi_static::DecodePacked<#lifetime>>::decode_packed_prefix(buf)?
+                }
+            }
+        };
+
+        if is_ignored(&f.attrs) {
+            let value = default_value(f)?;
+            decode_stmts.push(quote! {
+                let (_, buf) = #decode;
+            });
+            field_values.push(quote! { #f_name: #value, });
+        } else {
+            decode_stmts.push(quote! {
+                let (#f_name, buf) = #decode;
+            });
+            field_values.push(quote! { #f_name, });
+        }
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::DecodePacked<#lifetime> for #name #ty_generics #where_clause {
+                fn decode_packed_prefix(buf: &#lifetime [u8]) -> Result<(Self, &#lifetime [u8]), ()> {
+                    #(#decode_stmts)*
+                    Ok((Self {
+                        #(#field_values)*
+                    }, buf))
+                }
+            }
+        };
+    })
+}
+
+/// The packed width in bytes of a field with `#[ethabi(abi_type = "uintN")]`
+///
+/// `Err` for any other `abi_type` or a width over that of a Rust uint field
+fn packed_uint_bytes(f: &Field) -> syn::Result<Option<usize>> {
+    let abi_type = match find_str_value(&f.attrs, "abi_type") {
+        Some(abi_type) => abi_type,
+        None => return Ok(None),
+    };
+    let bits = abi_type
+        .value()
+        .strip_prefix("uint")
+        .and_then(|bits| bits.parse::<usize>().ok())
+        .filter(|bits| bits.is_multiple_of(8) && (8..=256).contains(bits))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &abi_type,
+                "packed `abi_type` must be a `uintN` e.g. \"uint24\"",
+            )
+        })?;
+    if let Some(size) = uint_size(&f.ty) {
+        if bits > size * 8 {
+            return Err(syn::Error::new_spanned(
+                &abi_type,
+                format!(
+                    "`{}` does not fit in `{}`",
+                    abi_type.value(),
+                    f.ty.to_token_stream()
+                ),
+            ));
+        }
+    }
+    Ok(Some(bits / 8))
+}
+
+/// The size in bytes of a Rust uint type, `None` for other types
+fn uint_size(ty: &Type) -> Option<usize> {
+    let ident = match ty {
+        Type::Path(path) => path.path.get_ident()?,
+        _ => return None,
+    };
+    ["u8", "u16", "u32", "u64", "u128"]
+        .iter()
+        .position(|uint| ident == uint)
+        .map(|idx| 1 << idx)
+}
@@ -0,0 +1,125 @@
+//! Derives for conversions to/from `ethabi::Token`s
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Field, GenericParam, Lifetime, LifetimeDef};
+
+use crate::{default_value, is_ignored, is_rust_only, named_fields, reject_flatten};
+
+/// Generate `IntoTokens` and `IntoToken` impls, `rust_only` fields are dropped
+pub(crate) fn impl_into_tokens(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let abi_fields: Vec<&Field> = named_fields(input)?
+        .named
+        .iter()
+        .filter(|f| !is_rust_only(&f.attrs))
+        .collect();
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for f in abi_fields.iter() {
+        reject_flatten(f, "IntoTokens")?;
+        let f_type = &f.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(#f_type: _ethabi_static::IntoToken));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let len = abi_fields.len();
+    let f_names = abi_fields.iter().map(|f| &f.ident);
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::IntoTokens for #name #ty_generics #where_clause {
+                fn into_tokens(self) -> _ethabi_static::__private::Vec<_ethabi_static::ethabi::Token> {
+                    let mut tokens = _ethabi_static::__private::Vec::with_capacity(#len);
+                    #(tokens.push(_ethabi_static::IntoToken::into_token(self.#f_names));)*
+                    tokens
+                }
+            }
+            impl #impl_generics _ethabi_static::IntoToken for #name #ty_generics #where_clause {
+                fn into_token(self) -> _ethabi_static::ethabi::Token {
+                    _ethabi_static::ethabi::Token::Tuple(_ethabi_static::IntoTokens::into_tokens(self))
+                }
+            }
+        };
+    })
+}
+
+/// Generate `FromTokens` and `FromToken` impls borrowing from the tokens
+///
+/// Ignored fields consume a token but use their default, `rust_only` fields consume none
+pub(crate) fn impl_from_tokens(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let fields = named_fields(input)?;
+
+    // borrow for the struct's own lifetime if it has one
+    let mut generics = input.generics.clone();
+    let lifetime: Lifetime = match generics.lifetimes().next() {
+        Some(def) => def.lifetime.clone(),
+        None => {
+            let lifetime: Lifetime = parse_quote!('__t);
+            generics.params.insert(
+                0,
+                GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+            );
+            lifetime
+        }
+    };
+
+    let mut slot = 0_usize;
+    let mut field_values = Vec::<TokenStream>::with_capacity(fields.named.len());
+    let where_clause = generics.make_where_clause();
+    for f in fields.named.iter() {
+        let f_name = &f.ident;
+        if is_rust_only(&f.attrs) {
+            let value = default_value(f)?;
+            field_values.push(quote! { #f_name: #value, });
+            continue;
+        }
+        reject_flatten(f, "FromTokens")?;
+        slot += 1;
+        if is_ignored(&f.attrs) {
+            let value = default_value(f)?;
+            field_values.push(quote! { #f_name: #value, });
+            continue;
+        }
+        let f_type = &f.ty;
+        let idx = slot - 1;
+        where_clause
+            .predicates
+            .push(parse_quote!(#f_type: _ethabi_static::FromToken<#lifetime>));
+        field_values.push(quote! {
+            #f_name: _ethabi_static::FromToken::from_token(&tokens[#idx])?,
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::FromTokens<#lifetime> for #name #ty_generics #where_clause {
+                fn from_tokens(tokens: &#lifetime [_ethabi_static::ethabi::Token]) -> Result<Self, ()> {
+                    if tokens.len() != #slot {
+                        return Err(());
+                    }
+                    Ok(Self {
+                        #(#field_values)*
+                    })
+                }
+            }
+            impl #impl_generics _ethabi_static::FromToken<#lifetime> for #name #ty_generics #where_clause {
+                fn from_token(token: &#lifetime _ethabi_static::ethabi::Token) -> Result<Self, ()> {
+                    match token {
+                        _ethabi_static::ethabi::Token::Tuple(tokens) => {
+                            _ethabi_static::FromTokens::from_tokens(tokens)
+                        }
+                        _ => Err(()),
+                    }
+                }
+            }
+        };
+    })
+}
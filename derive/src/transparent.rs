@@ -0,0 +1,125 @@
+//! Derive for `#[ethabi(transparent)]` newtypes encoded exactly as their one field
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Field, Fields, GenericParam, Lifetime};
+
+use crate::{abi_type_bound, find_str_value};
+
+/// Generate `DecodeStatic` and `AbiType` impls delegating to the struct's only field, e.g. a
+/// Solidity user defined value type `type Fee is uint24`
+///
+/// The field's `abi_type` attribute, if any, names the ABI type
+pub(crate) fn impl_transparent(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let field = only_field(input)?;
+    let f_type = &field.ty;
+    let (wrap, member) = match field.ident {
+        Some(ref f_name) => (quote! { #name { #f_name: inner } }, quote! { #f_name }),
+        None => (quote! { #name(inner) }, quote! { 0 }),
+    };
+
+    // decode with the struct's lifetime, or a fresh one if it borrows nothing
+    let mut generics = input.generics.clone();
+    let lifetime: Lifetime = match generics.lifetimes().next() {
+        Some(def) => def.lifetime.clone(),
+        None => {
+            let lifetime: Lifetime = parse_quote!('a);
+            generics
+                .params
+                .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+            lifetime
+        }
+    };
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#f_type: _ethabi_static::DecodeStatic<#lifetime>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut abi_generics = input.generics.clone();
+    let write_type = match find_str_value(&field.attrs, "abi_type") {
+        Some(abi_type) => quote! { f.write_str(#abi_type) },
+        None => {
+            abi_generics
+                .make_where_clause()
+                .predicates
+                .push(abi_type_bound(f_type));
+            quote! { <#f_type as _ethabi_static::AbiType>::write_abi_type(f) }
+        }
+    };
+    let (abi_impl_generics, _, abi_where_clause) = abi_generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            extern crate ethabi_static as _ethabi_static;
+            impl #impl_generics _ethabi_static::DecodeStatic<#lifetime> for #name #ty_generics #where_clause {
+                const DYNAMIC: bool = <#f_type as _ethabi_static::DecodeStatic<#lifetime>>::DYNAMIC;
+                const HEAD_SIZE: usize = <#f_type as _ethabi_static::DecodeStatic<#lifetime>>::HEAD_SIZE;
+                fn decode_static(buf: &#lifetime [u8], offset: usize) -> Result<Self, ()> {
+                    let inner = <#f_type as _ethabi_static::DecodeStatic>::decode_static(buf, offset)?;
+                    Ok(#wrap)
+                }
+                fn decode_from_head(buf: &#lifetime [u8], base: usize, head_offset: usize) -> Result<Self, ()> {
+                    let inner = <#f_type as _ethabi_static::DecodeStatic>::decode_from_head(buf, base, head_offset)?;
+                    Ok(#wrap)
+                }
+                fn decode(buf: &#lifetime [u8]) -> Result<Self, ()> {
+                    let inner = <#f_type as _ethabi_static::DecodeStatic>::decode(buf)?;
+                    Ok(#wrap)
+                }
+                fn decode_static_strict(buf: &#lifetime [u8], offset: usize) -> Result<Self, ()> {
+                    let inner = <#f_type as _ethabi_static::DecodeStatic>::decode_static_strict(buf, offset)?;
+                    Ok(#wrap)
+                }
+                fn decode_from_head_strict(buf: &#lifetime [u8], base: usize, head_offset: usize) -> Result<Self, ()> {
+                    let inner = <#f_type as _ethabi_static::DecodeStatic>::decode_from_head_strict(buf, base, head_offset)?;
+                    Ok(#wrap)
+                }
+                fn decode_strict(buf: &#lifetime [u8]) -> Result<Self, ()> {
+                    let inner = <#f_type as _ethabi_static::DecodeStatic>::decode_strict(buf)?;
+                    Ok(#wrap)
+                }
+                fn decode_tail_strict(buf: &#lifetime [u8], offset: usize) -> Result<(Self, usize), ()> {
+                    let (inner, end) = <#f_type as _ethabi_static::DecodeStatic>::decode_tail_strict(buf, offset)?;
+                    Ok((#wrap, end))
+                }
+                fn decode_static_into(&mut self, buf: &#lifetime [u8], offset: usize) -> Result<(), ()> {
+                    _ethabi_static::DecodeStatic::decode_static_into(&mut self.#member, buf, offset)
+                }
+                fn decode_from_head_into(&mut self, buf: &#lifetime [u8], base: usize, head_offset: usize) -> Result<(), ()> {
+                    _ethabi_static::DecodeStatic::decode_from_head_into(&mut self.#member, buf, base, head_offset)
+                }
+                fn decode_into(&mut self, buf: &#lifetime [u8]) -> Result<(), ()> {
+                    _ethabi_static::DecodeStatic::decode_into(&mut self.#member, buf)
+                }
+            }
+            impl #abi_impl_generics _ethabi_static::AbiType for #name #ty_generics #abi_where_clause {
+                fn write_abi_type<__W: ::core::fmt::Write>(f: &mut __W) -> ::core::fmt::Result {
+                    #write_type
+                }
+            }
+        };
+    })
+}
+
+/// The field of a struct with exactly one field, named or not
+fn only_field(input: &DeriveInput) -> syn::Result<&Field> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`transparent` requires a struct with exactly one field",
+            ))
+        }
+    };
+    match fields {
+        Fields::Named(named) if named.named.len() == 1 => Ok(&named.named[0]),
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => Ok(&unnamed.unnamed[0]),
+        _ => Err(syn::Error::new(
+            input.ident.span(),
+            "`transparent` requires a struct with exactly one field",
+        )),
+    }
+}
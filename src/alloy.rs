@@ -0,0 +1,187 @@
+//! `DecodeStatic` impls and conversions for `alloy-primitives` types
+use core::fmt::{self, Write};
+
+use alloy_primitives::{Address, Bytes, FixedBytes, I256, U256};
+
+use crate::owned::impl_into_owned_identity;
+use crate::{
+    keccak256, slice_as_array, AbiType, AddressZcp, BytesZcp, DecodeStatic, Eip712Field,
+    FixedBytesZcp, IntoOwned,
+};
+
+impl<'a> DecodeStatic<'a> for Address {
+    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(Address::from(AddressZcp::decode_static(buf, offset)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(Address::from(AddressZcp::decode_static_strict(
+            buf, offset,
+        )?))
+    }
+}
+
+impl<'a> DecodeStatic<'a> for U256 {
+    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        let word = buf.get(offset..offset + 32).ok_or(())?;
+        Ok(U256::from_be_bytes::<32>(*slice_as_array(word)))
+    }
+}
+
+impl<'a> DecodeStatic<'a> for I256 {
+    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(I256::from_raw(U256::decode_static(buf, offset)?))
+    }
+}
+
+impl<'a, const N: usize> DecodeStatic<'a> for FixedBytes<N> {
+    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(FixedBytes::from(FixedBytesZcp::<N>::decode_static(
+            buf, offset,
+        )?))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(FixedBytes::from(FixedBytesZcp::<N>::decode_static_strict(
+            buf, offset,
+        )?))
+    }
+}
+
+/// Copies the payload, prefer `BytesZcp` in hot paths
+impl<'a> DecodeStatic<'a> for Bytes {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Bytes::from(BytesZcp::decode_static(buf, len_offset)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Bytes::from(BytesZcp::decode_static_strict(
+            buf, len_offset,
+        )?))
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (bytes, end) = BytesZcp::decode_tail_strict(buf, len_offset)?;
+        Ok((Bytes::from(bytes), end))
+    }
+}
+
+impl AbiType for Address {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("address")
+    }
+}
+
+impl AbiType for U256 {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("uint256")
+    }
+}
+
+impl AbiType for I256 {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("int256")
+    }
+}
+
+impl<const N: usize> AbiType for FixedBytes<N> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        write!(f, "bytes{}", N)
+    }
+}
+
+impl AbiType for Bytes {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("bytes")
+    }
+}
+
+impl Eip712Field for Address {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Address as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.into_word().0
+    }
+}
+
+impl Eip712Field for U256 {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <U256 as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.to_be_bytes()
+    }
+}
+
+/// Two's complement
+impl Eip712Field for I256 {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <I256 as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.to_be_bytes()
+    }
+}
+
+/// Right padded to 32 bytes
+impl<const N: usize> Eip712Field for FixedBytes<N> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <FixedBytes<N> as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        FixedBytesZcp(&self.0).eip712_encode_value()
+    }
+}
+
+/// `keccak256` of the payload
+impl Eip712Field for Bytes {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Bytes as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        keccak256(self)
+    }
+}
+
+impl From<AddressZcp<'_>> for Address {
+    fn from(a: AddressZcp<'_>) -> Self {
+        Address::new(*a.0)
+    }
+}
+
+impl<'a> From<&'a Address> for AddressZcp<'a> {
+    fn from(a: &'a Address) -> Self {
+        AddressZcp(&a.0 .0)
+    }
+}
+
+impl<const N: usize> From<FixedBytesZcp<'_, N>> for FixedBytes<N> {
+    fn from(b: FixedBytesZcp<'_, N>) -> Self {
+        FixedBytes(*b.0)
+    }
+}
+
+impl<'a, const N: usize> From<&'a FixedBytes<N>> for FixedBytesZcp<'a, N> {
+    fn from(b: &'a FixedBytes<N>) -> Self {
+        FixedBytesZcp(&b.0)
+    }
+}
+
+impl From<BytesZcp<'_>> for Bytes {
+    fn from(b: BytesZcp<'_>) -> Self {
+        Bytes::copy_from_slice(b.0)
+    }
+}
+
+impl<'a> From<&'a Bytes> for BytesZcp<'a> {
+    fn from(b: &'a Bytes) -> Self {
+        BytesZcp(b.as_ref())
+    }
+}
+
+impl_into_owned_identity!(Address, U256, I256, Bytes);
+
+impl<const N: usize> IntoOwned for FixedBytes<N> {
+    type Owned = FixedBytes<N>;
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
@@ -0,0 +1,56 @@
+//! `ArrayVec` backed `T[]` fields of at most `CAP` elements, without allocating
+use core::fmt::{self, Write};
+
+use arrayvec::ArrayVec;
+
+use crate::types::{decode_elements, decode_elements_strict_into, Elements};
+use crate::{AbiType, DecodeStatic, IntoOwned};
+
+/// `Err` past capacity
+impl<T, const CAP: usize> Elements<T> for ArrayVec<T, CAP> {
+    fn reserve_elements(&mut self, len: usize) -> Result<(), ()> {
+        if len > self.remaining_capacity() {
+            return Err(());
+        }
+        Ok(())
+    }
+    fn push_element(&mut self, item: T) {
+        self.push(item);
+    }
+}
+
+/// `T[]`, `Err` if the array has more than `CAP` elements
+impl<'a, T: DecodeStatic<'a>, const CAP: usize> DecodeStatic<'a> for ArrayVec<T, CAP> {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let mut items = ArrayVec::new();
+        decode_elements(&mut items, buf, len_offset)?;
+        Ok(items)
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let mut items = ArrayVec::new();
+        let end = decode_elements_strict_into(&mut items, buf, len_offset)?;
+        Ok((items, end))
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], len_offset: usize) -> Result<(), ()> {
+        self.clear();
+        decode_elements(self, buf, len_offset)
+    }
+}
+
+impl<T: AbiType, const CAP: usize> AbiType for ArrayVec<T, CAP> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_abi_type(f)?;
+        f.write_str("[]")
+    }
+}
+
+impl<T: IntoOwned, const CAP: usize> IntoOwned for ArrayVec<T, CAP> {
+    type Owned = ArrayVec<T::Owned, CAP>;
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
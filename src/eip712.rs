@@ -0,0 +1,320 @@
+//! EIP-712 typed structured data hashing
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use ethereum_types::{H160, U256};
+
+use crate::{
+    keccak256, AbiType, AddressZcp, Array, BytesZcp, FixedArrayZcp, FixedBytesZcp, StrZcp, Tuple,
+    Tuples, Word,
+};
+
+/// A member type of an EIP-712 struct
+pub trait Eip712Field {
+    /// Write the member's type name as it appears in `encodeType` e.g. `uint256`, `Mail[]`
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result;
+
+    /// Add the `encodeType` of each struct type referenced by this member, keyed by struct name
+    fn eip712_struct_types(_types: &mut BTreeMap<&'static str, String>) {}
+
+    /// The member's 32 byte `encodeData` value
+    fn eip712_encode_value(&self) -> [u8; 32];
+}
+
+/// An EIP-712 struct, see `#[derive(Eip712)]`
+pub trait Eip712: Eip712Field {
+    /// The struct's name in `encodeType`
+    const NAME: &'static str;
+
+    /// Write the struct's own type e.g. `Mail(address from,address to,string contents)`
+    fn write_eip712_struct_type<W: Write>(f: &mut W) -> fmt::Result;
+
+    /// Append the `encodeData` of each member
+    fn eip712_encode_data(&self, out: &mut Vec<u8>);
+
+    /// `encodeType` i.e. the struct's type followed by referenced struct types sorted by name
+    fn encode_type() -> String {
+        let mut types = BTreeMap::new();
+        Self::eip712_struct_types(&mut types);
+        let mut encoded = types.remove(Self::NAME).unwrap_or_default();
+        for referenced in types.values() {
+            encoded.push_str(referenced);
+        }
+        encoded
+    }
+
+    /// `keccak256(encodeType)`
+    fn type_hash() -> [u8; 32] {
+        keccak256(Self::encode_type().as_bytes())
+    }
+
+    /// `keccak256(typeHash ‖ encodeData(self))`
+    fn hash_struct(&self) -> [u8; 32] {
+        let mut data = Vec::with_capacity(32 * 8);
+        data.extend_from_slice(&Self::type_hash());
+        self.eip712_encode_data(&mut data);
+        keccak256(&data)
+    }
+
+    /// The digest to sign i.e. `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(self))`
+    fn signing_hash(&self, domain: &Eip712Domain) -> [u8; 32] {
+        let mut data = [0_u8; 66];
+        data[..2].copy_from_slice(b"\x19\x01");
+        data[2..34].copy_from_slice(&domain.separator());
+        data[34..].copy_from_slice(&self.hash_struct());
+        keccak256(&data)
+    }
+}
+
+/// The `EIP712Domain` of a signature, only the fields that are set are part of the domain type
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Eip712Domain<'a> {
+    pub name: Option<&'a str>,
+    pub version: Option<&'a str>,
+    pub chain_id: Option<U256>,
+    pub verifying_contract: Option<H160>,
+    pub salt: Option<[u8; 32]>,
+}
+
+impl Eip712Domain<'_> {
+    /// `hashStruct(domain)`
+    pub fn separator(&self) -> [u8; 32] {
+        let mut members = Vec::with_capacity(5);
+        let mut data = Vec::with_capacity(32 * 6);
+        data.extend_from_slice(&[0_u8; 32]);
+        if let Some(name) = self.name {
+            members.push("string name");
+            data.extend_from_slice(&name.eip712_encode_value());
+        }
+        if let Some(version) = self.version {
+            members.push("string version");
+            data.extend_from_slice(&version.eip712_encode_value());
+        }
+        if let Some(chain_id) = self.chain_id {
+            members.push("uint256 chainId");
+            data.extend_from_slice(&chain_id.eip712_encode_value());
+        }
+        if let Some(verifying_contract) = self.verifying_contract {
+            members.push("address verifyingContract");
+            data.extend_from_slice(&verifying_contract.eip712_encode_value());
+        }
+        if let Some(salt) = self.salt {
+            members.push("bytes32 salt");
+            data.extend_from_slice(&salt);
+        }
+        let mut encode_type = String::from("EIP712Domain(");
+        encode_type.push_str(&members.join(","));
+        encode_type.push(')');
+        data[..32].copy_from_slice(&keccak256(encode_type.as_bytes()));
+        keccak256(&data)
+    }
+}
+
+/// A uint left padded to 32 bytes
+fn uint_word(be: &[u8]) -> [u8; 32] {
+    let mut word = [0_u8; 32];
+    word[32 - be.len()..].copy_from_slice(be);
+    word
+}
+
+macro_rules! impl_eip712_uint {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Eip712Field for $t {
+                fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+                    <$t as AbiType>::write_abi_type(f)
+                }
+                fn eip712_encode_value(&self) -> [u8; 32] {
+                    uint_word(&self.to_be_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_eip712_uint!(u8, u16, u32, u64, u128);
+
+impl Eip712Field for bool {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <bool as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        uint_word(&[*self as u8])
+    }
+}
+
+impl Eip712Field for U256 {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <U256 as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        let mut word = [0_u8; 32];
+        // limbs are little endian
+        for (chunk, limb) in word.chunks_exact_mut(8).zip(self.0.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        word
+    }
+}
+
+impl Eip712Field for AddressZcp<'_> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <AddressZcp as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        uint_word(self.0)
+    }
+}
+
+impl Eip712Field for H160 {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("address")
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        uint_word(self.as_bytes())
+    }
+}
+
+/// Right padded to 32 bytes
+impl<const N: usize> Eip712Field for FixedBytesZcp<'_, N> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <FixedBytesZcp<N> as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        let mut word = [0_u8; 32];
+        word[..N].copy_from_slice(self.0);
+        word
+    }
+}
+
+impl Eip712Field for Word {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Word as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// `keccak256` of the payload
+impl Eip712Field for BytesZcp<'_> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <BytesZcp as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        keccak256(self.0)
+    }
+}
+
+/// `keccak256` of the UTF-8 bytes
+impl Eip712Field for StrZcp<'_> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <StrZcp as AbiType>::write_abi_type(f)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        keccak256(self.0.as_bytes())
+    }
+}
+
+/// `keccak256` of the UTF-8 bytes
+impl Eip712Field for &str {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("string")
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        keccak256(self.as_bytes())
+    }
+}
+
+/// `keccak256` of the UTF-8 bytes
+impl Eip712Field for String {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("string")
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        keccak256(self.as_bytes())
+    }
+}
+
+/// `keccak256` of the concatenated element values
+fn hash_elements<'t, T: Eip712Field + 't>(items: impl Iterator<Item = &'t T>) -> [u8; 32] {
+    let mut data = Vec::with_capacity(items.size_hint().0 * 32);
+    for item in items {
+        data.extend_from_slice(&item.eip712_encode_value());
+    }
+    keccak256(&data)
+}
+
+impl<T: Eip712Field> Eip712Field for Vec<T> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_eip712_type(f)?;
+        f.write_str("[]")
+    }
+    fn eip712_struct_types(types: &mut BTreeMap<&'static str, String>) {
+        T::eip712_struct_types(types)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        hash_elements(self.iter())
+    }
+}
+
+impl<T: Eip712Field> Eip712Field for Array<T> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Vec<T>>::write_eip712_type(f)
+    }
+    fn eip712_struct_types(types: &mut BTreeMap<&'static str, String>) {
+        T::eip712_struct_types(types)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.0.eip712_encode_value()
+    }
+}
+
+impl<T: Eip712Field> Eip712Field for Tuples<T> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Vec<T>>::write_eip712_type(f)
+    }
+    fn eip712_struct_types(types: &mut BTreeMap<&'static str, String>) {
+        T::eip712_struct_types(types)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.0.eip712_encode_value()
+    }
+}
+
+impl<T: Eip712Field, const N: usize> Eip712Field for [T; N] {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_eip712_type(f)?;
+        write!(f, "[{}]", N)
+    }
+    fn eip712_struct_types(types: &mut BTreeMap<&'static str, String>) {
+        T::eip712_struct_types(types)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        hash_elements(self.iter())
+    }
+}
+
+impl<T: Eip712Field, const N: usize> Eip712Field for FixedArrayZcp<'_, N, T> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        <[T; N]>::write_eip712_type(f)
+    }
+    fn eip712_struct_types(types: &mut BTreeMap<&'static str, String>) {
+        T::eip712_struct_types(types)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.0.eip712_encode_value()
+    }
+}
+
+impl<T: Eip712Field> Eip712Field for Tuple<T> {
+    fn write_eip712_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_eip712_type(f)
+    }
+    fn eip712_struct_types(types: &mut BTreeMap<&'static str, String>) {
+        T::eip712_struct_types(types)
+    }
+    fn eip712_encode_value(&self) -> [u8; 32] {
+        self.0.eip712_encode_value()
+    }
+}
@@ -0,0 +1,77 @@
+//! Hex formatting and parsing helpers
+use core::fmt;
+
+use ethereum_types::H160;
+
+use crate::packed::keccak256;
+
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+/// Formats bytes as lowercase hex without a prefix
+pub(crate) struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// EIP-55 checksummed hex of an address, `0x` prefixed
+pub(crate) fn checksum(address: &[u8; 20]) -> [u8; 42] {
+    let mut out = [0_u8; 42];
+    out[0] = b'0';
+    out[1] = b'x';
+    for (idx, byte) in address.iter().enumerate() {
+        out[2 + idx * 2] = HEX_CHARS[(byte >> 4) as usize];
+        out[3 + idx * 2] = HEX_CHARS[(byte & 0x0f) as usize];
+    }
+
+    // uppercase any letter whose nibble in keccak(lowercase hex) is >= 8
+    let hash = keccak256(&out[2..]);
+    for (idx, c) in out[2..].iter_mut().enumerate() {
+        let nibble = if idx % 2 == 0 {
+            hash[idx / 2] >> 4
+        } else {
+            hash[idx / 2] & 0x0f
+        };
+        if nibble >= 8 {
+            c.make_ascii_uppercase();
+        }
+    }
+
+    out
+}
+
+/// Parse a `0x` prefixed hex address, `Err` if it is mixed case but not EIP-55 checksummed
+///
+/// All lowercase or all uppercase hex has no checksum and is accepted
+pub fn parse_checksummed(s: &str) -> Result<H160, ()> {
+    let hex = s.strip_prefix("0x").ok_or(())?.as_bytes();
+    if hex.len() != 40 {
+        return Err(());
+    }
+    let mut address = [0_u8; 20];
+    for (byte, pair) in address.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+
+    let mixed_case =
+        hex.iter().any(u8::is_ascii_lowercase) && hex.iter().any(u8::is_ascii_uppercase);
+    if mixed_case && checksum(&address)[2..] != *hex {
+        return Err(());
+    }
+    Ok(H160(address))
+}
+
+/// The value of a hex digit, either case
+fn nibble(c: u8) -> Result<u8, ()> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(()),
+    }
+}
@@ -1,3 +1,61 @@
+//! Statically generated Ethereum ABI decoding
+//!
+//! `#![no_std]` compatible, `Vec` backed types require the `alloc` feature (enabled by `std`)
+#![no_std]
+#![allow(clippy::result_unit_err)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub use ethabi_static_derive::*;
+#[cfg(feature = "alloy")]
+mod alloy;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "alloc")]
+mod eip712;
+mod hex;
+#[cfg(feature = "alloc")]
+mod multicall;
+mod owned;
+mod packed;
+#[cfg(feature = "serde")]
+mod serde;
+mod signature;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+mod strict;
+#[cfg(feature = "ethabi")]
+mod tokens;
 mod types;
+mod uniswap;
+#[cfg(feature = "alloc")]
+pub use eip712::*;
+#[cfg(feature = "ethabi")]
+pub use ethabi;
+pub use hex::parse_checksummed;
+#[cfg(feature = "alloc")]
+pub use multicall::*;
+pub use owned::*;
+pub use packed::*;
+pub use signature::*;
+#[cfg(feature = "ethabi")]
+pub use tokens::*;
 pub use types::*;
+pub use uniswap::*;
+
+/// Builds the README's first example, the rest are `ignore`d snippets
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctest;
+
+/// Re-exports for derive generated code, not public API
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::packed::PackedUint;
+    pub use crate::strict::{narrow_head_end, TailCursor};
+    pub use crate::types::assert_static;
+    #[cfg(feature = "alloc")]
+    pub use alloc::{collections::BTreeMap, string::String, vec::Vec};
+}
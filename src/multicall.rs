@@ -0,0 +1,293 @@
+//! Multicall3 `aggregate3` calldata encoding and result decoding
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Write},
+    ops::Deref,
+};
+
+use ethereum_types::H160;
+
+use crate::{
+    as_usize,
+    strict::{strict_usize, TailCursor},
+    types::check_head,
+    AbiType, BytesZcp, DecodeStatic, Wrapped,
+};
+
+/// Multicall3 deployment address, the same on most chains
+pub const MULTICALL3_ADDRESS: H160 = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+/// `aggregate3((address,bool,bytes)[])`
+pub const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// One call of an `aggregate3` batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call3 {
+    pub target: H160,
+    /// Whether the batch continues if this call reverts
+    pub allow_failure: bool,
+    pub call_data: Vec<u8>,
+}
+
+/// Builds `aggregate3` calldata
+///
+/// ```ignore
+/// let calldata = Aggregate3::new()
+///     .add_call(pair_a, true, GET_RESERVES.to_vec())
+///     .add_call(pair_b, true, GET_RESERVES.to_vec())
+///     .encode();
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Aggregate3 {
+    pub calls: Vec<Call3>,
+}
+
+impl Aggregate3 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a call to `target` with the given (selector prefixed) `call_data`
+    pub fn add_call(mut self, target: H160, allow_failure: bool, call_data: Vec<u8>) -> Self {
+        self.calls.push(Call3 {
+            target,
+            allow_failure,
+            call_data,
+        });
+        self
+    }
+
+    /// ABI encode the `aggregate3` calldata, selector included
+    pub fn encode(&self) -> Vec<u8> {
+        // each call is 3 head words, a length word and its padded data
+        let tails_size: usize = self
+            .calls
+            .iter()
+            .map(|c| 4 * 32 + padded_len(c.call_data.len()))
+            .sum();
+        let mut out = Vec::with_capacity(4 + 3 * 32 + 32 * self.calls.len() + tails_size);
+        out.extend_from_slice(&AGGREGATE3_SELECTOR);
+        push_word(&mut out, 32);
+        push_word(&mut out, self.calls.len());
+
+        // element offsets are relative to the first element head
+        let mut offset = 32 * self.calls.len();
+        for call in self.calls.iter() {
+            push_word(&mut out, offset);
+            offset += 4 * 32 + padded_len(call.call_data.len());
+        }
+        for call in self.calls.iter() {
+            out.extend_from_slice(&[0_u8; 12]);
+            out.extend_from_slice(call.target.as_bytes());
+            push_word(&mut out, call.allow_failure as usize);
+            push_word(&mut out, 3 * 32);
+            push_word(&mut out, call.call_data.len());
+            out.extend_from_slice(&call.call_data);
+            out.resize(
+                out.len() + padded_len(call.call_data.len()) - call.call_data.len(),
+                0,
+            );
+        }
+
+        out
+    }
+}
+
+/// Why a call in the batch has no result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallError<'a> {
+    /// The call reverted with the given data
+    Reverted(BytesZcp<'a>),
+    /// The call succeeded without return data e.g. the target has no code
+    NoData,
+}
+
+/// The result of one call in an `aggregate3` batch, its return data decoded as `T`
+pub type CallResult<'a, T> = Result<Wrapped<T>, CallError<'a>>;
+
+/// `aggregate3` return data `(bool,bytes)[]` with each successful call's return data decoded as `T`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregate3Results<'a, T>(pub Vec<CallResult<'a, T>>);
+
+impl<'a, T> Deref for Aggregate3Results<'a, T> {
+    type Target = Vec<CallResult<'a, T>>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T> IntoIterator for Aggregate3Results<'a, T> {
+    type Item = CallResult<'a, T>;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Aggregate3Results<'a, T> {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let raw = RawResults::new(buf, len_offset)?;
+        let mut results = Vec::with_capacity(raw.len.min(raw.heads.len() / 32));
+        for result in raw {
+            results.push(decode_call(result?, false)?);
+        }
+        Ok(Self(results))
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    /// Each `(bool,bytes)` and the return data of each successful call are decoded strictly
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let len = strict_usize(buf, len_offset)?;
+        let body = len_offset + 32;
+        let head_size = len.checked_mul(32).ok_or(())?;
+        if body.checked_add(head_size).ok_or(())? > buf.len() {
+            return Err(());
+        }
+        let mut results = Vec::with_capacity(len);
+        let mut tails = TailCursor::new(head_size);
+        for i in 0..len {
+            let tuple_offset = body + tails.next(buf, body + i * 32)?;
+            let success = bool::decode_static_strict(buf, tuple_offset)?;
+            // the bytes' tail follows the tuple's two head words
+            let data_offset = tuple_offset + TailCursor::new(64).next(buf, tuple_offset + 32)?;
+            let (BytesZcp(data), end) = BytesZcp::decode_tail_strict(buf, data_offset)?;
+            tails.end(end - body);
+            results.push(decode_call((success, data), true)?);
+        }
+        Ok((Self(results), body + tails.tail_end()))
+    }
+}
+
+impl<T> AbiType for Aggregate3Results<'_, T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("(bool,bytes)[]")
+    }
+}
+
+/// `aggregate3` return data decoded into a different type per call
+///
+/// Implemented for tuples of `CallResult`s, the batch must have exactly one result per element
+/// ```ignore
+/// let (reserves, balance): (CallResult<UniswapV2Reserves>, CallResult<U256>) =
+///     Aggregate3Batch::decode_aggregate3(&return_data)?;
+/// ```
+pub trait Aggregate3Batch<'a>: Sized {
+    fn decode_aggregate3(buf: &'a [u8]) -> Result<Self, ()>;
+}
+
+macro_rules! impl_aggregate3_batch {
+    ($($len:literal => ($($t:ident),+)),* $(,)?) => {
+        $(
+            impl<'a, $($t: DecodeStatic<'a>),+> Aggregate3Batch<'a> for ($(CallResult<'a, $t>,)+) {
+                fn decode_aggregate3(buf: &'a [u8]) -> Result<Self, ()> {
+                    let len_offset = as_usize(buf.get(..32).ok_or(())?);
+                    let mut raw = RawResults::new(buf, len_offset)?;
+                    if raw.len != $len {
+                        return Err(());
+                    }
+                    Ok(($(decode_call::<$t>(raw.next().ok_or(())??, false)?,)+))
+                }
+            }
+        )*
+    };
+}
+
+impl_aggregate3_batch!(
+    1 => (A),
+    2 => (A, B),
+    3 => (A, B, C),
+    4 => (A, B, C, D),
+    5 => (A, B, C, D, E),
+    6 => (A, B, C, D, E, F),
+    7 => (A, B, C, D, E, F, G),
+    8 => (A, B, C, D, E, F, G, H),
+    9 => (A, B, C, D, E, F, G, H, I),
+    10 => (A, B, C, D, E, F, G, H, I, J),
+    11 => (A, B, C, D, E, F, G, H, I, J, K),
+    12 => (A, B, C, D, E, F, G, H, I, J, K, L),
+);
+
+/// Decode a call's return data as `T` if it succeeded, `Err` if it is too short for `T`'s head
+///
+/// `strict` decodes the return data with `decode_strict`
+fn decode_call<'a, T: DecodeStatic<'a>>(
+    (success, data): (bool, &'a [u8]),
+    strict: bool,
+) -> Result<CallResult<'a, T>, ()> {
+    Ok(match (success, data) {
+        (true, []) => Err(CallError::NoData),
+        (true, data) => {
+            check_head::<T>(data)?;
+            if strict {
+                Ok(Wrapped(T::decode_strict(data)?))
+            } else {
+                Ok(Wrapped(T::decode(data)?))
+            }
+        }
+        (false, data) => Err(CallError::Reverted(BytesZcp(data))),
+    })
+}
+
+/// The `(success, return data)` of each call in `aggregate3` return data
+struct RawResults<'a> {
+    /// the element heads onwards, element offsets are relative to here
+    heads: &'a [u8],
+    len: usize,
+    next: usize,
+}
+
+impl<'a> RawResults<'a> {
+    /// The `(bool,bytes)[]` whose length is at `len_offset`
+    fn new(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let len = as_usize(buf.get(len_offset..len_offset + 32).ok_or(())?);
+        let heads = buf.get(len_offset + 32..).ok_or(())?;
+        Ok(Self {
+            heads,
+            len,
+            next: 0,
+        })
+    }
+
+    /// Decode the `(bool,bytes)` whose offset is at `head_offset`
+    fn result_at(&self, head_offset: usize) -> Result<(bool, &'a [u8]), ()> {
+        let heads = self.heads;
+        let word = |offset: usize| heads.get(offset..offset + 32).ok_or(());
+        let tuple_offset = as_usize(word(head_offset)?);
+        let success = word(tuple_offset)?[31] == 1;
+        let len_offset = tuple_offset + as_usize(word(tuple_offset + 32)?);
+        let len = as_usize(word(len_offset)?);
+        let data = heads
+            .get(len_offset + 32..len_offset + 32 + len)
+            .ok_or(())?;
+        Ok((success, data))
+    }
+}
+
+impl<'a> Iterator for RawResults<'a> {
+    type Item = Result<(bool, &'a [u8]), ()>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.len {
+            return None;
+        }
+        let head_offset = self.next * 32;
+        self.next += 1;
+        Some(self.result_at(head_offset))
+    }
+}
+
+/// Length of `len` bytes padded to a whole word
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(32) * 32
+}
+
+/// Append `value` as a big endian word
+fn push_word(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&[0_u8; 24]);
+    out.extend_from_slice(&(value as u64).to_be_bytes());
+}
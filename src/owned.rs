@@ -0,0 +1,144 @@
+//! Owned counterparts of the zero-copy types
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use ethereum_types::{H160, U256};
+
+use crate::{
+    AddressZcp, FixedArrayZcp, FixedBytesZcp, Tuple, Word, Wrapped, WrappedCall, WrappedTuple,
+};
+#[cfg(feature = "alloc")]
+use crate::{Array, BytesZcp, StrZcp, Tuples};
+
+/// Convert a decoded value into an owned value independent of the input buffer
+///
+/// e.g. `AddressZcp -> H160`, `BytesZcp -> Vec<u8>`, `FixedBytesZcp -> [u8; N]`
+pub trait IntoOwned {
+    /// The owned counterpart of `Self`
+    type Owned;
+    /// Copy out any data borrowed from the input buffer
+    fn into_owned(self) -> Self::Owned;
+}
+
+macro_rules! impl_into_owned_identity {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl IntoOwned for $t {
+                type Owned = $t;
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )*
+    };
+}
+#[cfg(feature = "alloy")]
+pub(crate) use impl_into_owned_identity;
+
+impl_into_owned_identity!(bool, u8, u16, u32, u64, u128, U256, Word);
+
+impl IntoOwned for AddressZcp<'_> {
+    type Owned = H160;
+    fn into_owned(self) -> Self::Owned {
+        H160(*self.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl IntoOwned for BytesZcp<'_> {
+    type Owned = Vec<u8>;
+    fn into_owned(self) -> Self::Owned {
+        self.0.to_vec()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl IntoOwned for StrZcp<'_> {
+    type Owned = String;
+    fn into_owned(self) -> Self::Owned {
+        self.0.into()
+    }
+}
+
+impl<const N: usize> IntoOwned for FixedBytesZcp<'_, N> {
+    type Owned = [u8; N];
+    fn into_owned(self) -> Self::Owned {
+        *self.0
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned, const N: usize> IntoOwned for [T; N] {
+    type Owned = [T::Owned; N];
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: Clone + IntoOwned, const N: usize> IntoOwned for FixedArrayZcp<'_, N, T> {
+    type Owned = [T::Owned; N];
+    fn into_owned(self) -> Self::Owned {
+        self.0.clone().into_owned()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: IntoOwned> IntoOwned for Array<T> {
+    type Owned = Array<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Array(self.0.into_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: IntoOwned> IntoOwned for Tuples<T> {
+    type Owned = Tuples<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Tuples(self.0.into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Tuple<T> {
+    type Owned = Tuple<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Tuple(self.0.into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Wrapped<T> {
+    type Owned = Wrapped<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        Wrapped(self.0.into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for WrappedTuple<T> {
+    type Owned = WrappedTuple<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        WrappedTuple(self.0.into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for WrappedCall<T> {
+    type Owned = WrappedCall<T::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        WrappedCall {
+            selector: self.selector,
+            args: self.args.into_owned(),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! `abi.encodePacked` encoding and decoding
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use ethereum_types::{H160, U256};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::{
+    slice_as_array, strict::zero_padded, AddressZcp, BytesZcp, FixedBytesZcp, StrZcp, Word,
+};
+
+/// `abi.encodePacked` encoding i.e. values concatenated without padding
+#[cfg(feature = "alloc")]
+pub trait EncodePacked {
+    /// Append the packed encoding of `self` to `out`
+    fn encode_packed_to(&self, out: &mut Vec<u8>);
+
+    /// The packed encoding of `self`
+    fn encode_packed(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_packed_to(&mut out);
+        out
+    }
+}
+
+/// Decode `abi.encodePacked` data
+///
+/// Dynamic types e.g. `bytes` take the rest of the input so may only come last
+pub trait DecodePacked<'a>: Sized {
+    /// Decode from the front of `buf` giving the value and the remaining bytes
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()>;
+
+    /// Decode from the whole of `buf`, `Err` if any bytes remain
+    fn decode_packed(buf: &'a [u8]) -> Result<Self, ()> {
+        match Self::decode_packed_prefix(buf)? {
+            (value, []) => Ok(value),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Split the first `N` bytes off `buf`
+fn split_packed<const N: usize>(buf: &[u8]) -> Result<(&[u8; N], &[u8]), ()> {
+    if buf.len() < N {
+        return Err(());
+    }
+    let (bytes, rest) = buf.split_at(N);
+    Ok((slice_as_array(bytes), rest))
+}
+
+macro_rules! impl_packed_uint {
+    ($($t:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "alloc")]
+            impl EncodePacked for $t {
+                fn encode_packed_to(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_be_bytes());
+                }
+            }
+
+            impl<'a> DecodePacked<'a> for $t {
+                fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+                    let (bytes, rest) = split_packed(buf)?;
+                    Ok((<$t>::from_be_bytes(*bytes), rest))
+                }
+            }
+
+            impl PackedUint for $t {
+                fn decode_packed_uint(buf: &[u8], bytes: usize) -> Result<(Self, &[u8]), ()> {
+                    const SIZE: usize = core::mem::size_of::<$t>();
+                    let (low, rest) = split_packed_uint(buf, bytes, SIZE)?;
+                    let mut be = [0_u8; SIZE];
+                    be[SIZE - low.len()..].copy_from_slice(low);
+                    Ok((<$t>::from_be_bytes(be), rest))
+                }
+                #[cfg(feature = "alloc")]
+                fn encode_packed_uint(&self, bytes: usize, out: &mut Vec<u8>) {
+                    extend_packed_uint(&self.to_be_bytes(), bytes, out);
+                }
+            }
+        )*
+    };
+}
+
+impl_packed_uint!(u8, u16, u32, u64, u128);
+
+/// Uints packed into fewer bytes than their Rust type e.g. a `uint24` held in a `u32`
+///
+/// A width over the Rust type's is zero extended, decoding it is an `Err` if the value doesn't fit
+#[doc(hidden)]
+pub trait PackedUint: Sized {
+    fn decode_packed_uint(buf: &[u8], bytes: usize) -> Result<(Self, &[u8]), ()>;
+    #[cfg(feature = "alloc")]
+    fn encode_packed_uint(&self, bytes: usize, out: &mut Vec<u8>);
+}
+
+/// Split a `bytes` wide packed uint off `buf`, giving at most its low `size` bytes and the rest of
+/// `buf`. `Err` if any higher bytes are set
+fn split_packed_uint(buf: &[u8], bytes: usize, size: usize) -> Result<(&[u8], &[u8]), ()> {
+    if buf.len() < bytes {
+        return Err(());
+    }
+    let (value, rest) = buf.split_at(bytes);
+    let (high, low) = value.split_at(bytes.saturating_sub(size));
+    zero_padded(high)?;
+    Ok((low, rest))
+}
+
+/// Append the big endian uint `be` as `bytes` bytes, truncated or zero extended
+#[cfg(feature = "alloc")]
+fn extend_packed_uint(be: &[u8], bytes: usize, out: &mut Vec<u8>) {
+    let pad = bytes.saturating_sub(be.len());
+    out.resize(out.len() + pad, 0);
+    out.extend_from_slice(&be[be.len() - (bytes - pad)..]);
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for U256 {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        // limbs are little endian
+        for limb in self.0.iter().rev() {
+            out.extend_from_slice(&limb.to_be_bytes());
+        }
+    }
+}
+
+impl<'a> DecodePacked<'a> for U256 {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        let (bytes, rest) = split_packed::<32>(buf)?;
+        Ok((U256::from(bytes), rest))
+    }
+}
+
+impl PackedUint for U256 {
+    fn decode_packed_uint(buf: &[u8], bytes: usize) -> Result<(Self, &[u8]), ()> {
+        let (low, rest) = split_packed_uint(buf, bytes, 32)?;
+        Ok((U256::from_big_endian(low), rest))
+    }
+    #[cfg(feature = "alloc")]
+    fn encode_packed_uint(&self, bytes: usize, out: &mut Vec<u8>) {
+        extend_packed_uint(&self.encode_packed(), bytes, out);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for bool {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl<'a> DecodePacked<'a> for bool {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        let ([byte], rest) = split_packed::<1>(buf)?;
+        Ok((*byte == 1, rest))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for AddressZcp<'_> {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0);
+    }
+}
+
+impl<'a> DecodePacked<'a> for AddressZcp<'a> {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        let (bytes, rest) = split_packed(buf)?;
+        Ok((AddressZcp(bytes), rest))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for H160 {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> EncodePacked for FixedBytesZcp<'_, N> {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0);
+    }
+}
+
+impl<'a, const N: usize> DecodePacked<'a> for FixedBytesZcp<'a, N> {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        let (bytes, rest) = split_packed(buf)?;
+        Ok((FixedBytesZcp(bytes), rest))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for Word {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.0);
+    }
+}
+
+impl<'a> DecodePacked<'a> for Word {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        let (bytes, rest) = split_packed(buf)?;
+        Ok((Word(*bytes), rest))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for BytesZcp<'_> {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0);
+    }
+}
+
+/// Takes the rest of the input
+impl<'a> DecodePacked<'a> for BytesZcp<'a> {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        Ok((BytesZcp(buf), &[]))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodePacked for StrZcp<'_> {
+    fn encode_packed_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0.as_bytes());
+    }
+}
+
+/// Takes the rest of the input, which must be UTF-8
+impl<'a> DecodePacked<'a> for StrZcp<'a> {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        Ok((StrZcp(core::str::from_utf8(buf).map_err(|_| ())?), &[]))
+    }
+}
+
+/// keccak256 of `bytes`
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0_u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(bytes);
+    keccak.finalize(&mut hash);
+    hash
+}
+
+/// The CREATE2 address `keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..]`
+///
+/// e.g. a Uniswap V2 pair's salt is `keccak256(abi.encodePacked(token0, token1))`
+pub fn create2_address(deployer: &[u8; 20], salt: &[u8; 32], init_code_hash: &[u8; 32]) -> H160 {
+    let mut preimage = [0_u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(deployer);
+    preimage[21..53].copy_from_slice(salt);
+    preimage[53..].copy_from_slice(init_code_hash);
+    H160::from_slice(&keccak256(&preimage)[12..])
+}
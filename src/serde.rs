@@ -0,0 +1,91 @@
+//! `Serialize` impls for the zero-copy and helper types
+use crate::{
+    hex::Hex, AddressZcp, BytesZcp, FixedArrayZcp, FixedBytesZcp, StrZcp, Tuple, Word, Wrapped,
+    WrappedCall, WrappedTuple,
+};
+#[cfg(feature = "alloc")]
+use crate::{Array, Tuples};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+/// EIP-55 checksummed hex string
+impl Serialize for AddressZcp<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// `0x` prefixed hex string
+impl Serialize for BytesZcp<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("0x{}", Hex(self.0)))
+    }
+}
+
+impl Serialize for StrZcp<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+/// `0x` prefixed hex string
+impl<const N: usize> Serialize for FixedBytesZcp<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("0x{}", Hex(self.0)))
+    }
+}
+
+/// `0x` prefixed hex string
+impl Serialize for Word {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("0x{}", Hex(&self.0)))
+    }
+}
+
+impl<T: Serialize, const N: usize> Serialize for FixedArrayZcp<'_, N, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for Tuple<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serialize> Serialize for Tuples<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Serialized as the decoded `T` rather than the raw `bytes`
+impl<T: Serialize> Serialize for Wrapped<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for WrappedTuple<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// `{ "selector": "0x...", "args": ... }`
+impl<T: Serialize> Serialize for WrappedCall<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut call = serializer.serialize_struct("WrappedCall", 2)?;
+        call.serialize_field("selector", &FixedBytesZcp(&self.selector))?;
+        call.serialize_field("args", &self.args)?;
+        call.end()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serialize> Serialize for Array<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
@@ -0,0 +1,133 @@
+//! Solidity type names for building ABI signatures
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use ethereum_types::U256;
+
+use crate::{
+    AddressZcp, BytesZcp, FixedArrayZcp, FixedBytesZcp, StrZcp, Tuple, Word, Wrapped, WrappedCall,
+    WrappedTuple,
+};
+#[cfg(feature = "alloc")]
+use crate::{Array, Tuples};
+
+/// Provides the canonical Solidity type name of an ABI type
+pub trait AbiType {
+    /// Write the canonical Solidity type name e.g. `uint256`, `(address,bytes)[]`
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result;
+
+    /// Write a tuple's member types without the parens e.g. `address,uint256`, for a struct
+    /// flattened into its parent. Other types write their `write_abi_type`
+    fn write_abi_fields<W: Write>(f: &mut W) -> fmt::Result {
+        Self::write_abi_type(f)
+    }
+
+    /// The canonical Solidity type name e.g. `uint256`, `(address,bytes)[]`
+    #[cfg(feature = "alloc")]
+    fn abi_type() -> String {
+        let mut s = String::new();
+        Self::write_abi_type(&mut s).expect("String is infallible");
+        s
+    }
+
+    /// Function signature taking `Self` as its params tuple e.g. `transfer(address,uint256)`
+    #[cfg(feature = "alloc")]
+    fn signature(name: &str) -> String {
+        let mut s = String::from(name);
+        Self::write_abi_type(&mut s).expect("String is infallible");
+        s
+    }
+}
+
+macro_rules! impl_abi_type {
+    ($($t:ty => $name:literal),* $(,)?) => {
+        $(
+            impl AbiType for $t {
+                fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+                    f.write_str($name)
+                }
+            }
+        )*
+    };
+}
+
+impl_abi_type!(
+    bool => "bool",
+    u8 => "uint8",
+    u16 => "uint16",
+    u32 => "uint32",
+    u64 => "uint64",
+    u128 => "uint128",
+    U256 => "uint256",
+    AddressZcp<'_> => "address",
+    BytesZcp<'_> => "bytes",
+    StrZcp<'_> => "string",
+    Word => "bytes32",
+);
+
+impl<const N: usize> AbiType for FixedBytesZcp<'_, N> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        write!(f, "bytes{}", N)
+    }
+}
+
+impl<T: AbiType, const N: usize> AbiType for [T; N] {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_abi_type(f)?;
+        write!(f, "[{}]", N)
+    }
+}
+
+impl<T: AbiType, const N: usize> AbiType for FixedArrayZcp<'_, N, T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        <[T; N]>::write_abi_type(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AbiType> AbiType for Vec<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_abi_type(f)?;
+        f.write_str("[]")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AbiType> AbiType for Array<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Vec<T>>::write_abi_type(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AbiType> AbiType for Tuples<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Vec<T>>::write_abi_type(f)
+    }
+}
+
+impl<T: AbiType> AbiType for Tuple<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        T::write_abi_type(f)
+    }
+}
+
+/// `T` is carried ABI encoded inside `bytes`
+impl<T> AbiType for Wrapped<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("bytes")
+    }
+}
+
+impl<T> AbiType for WrappedTuple<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("bytes")
+    }
+}
+
+impl<T> AbiType for WrappedCall<T> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        f.write_str("bytes")
+    }
+}
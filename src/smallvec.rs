@@ -0,0 +1,53 @@
+//! `SmallVec` backed `T[]` fields, inline up to `N` elements
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use smallvec::SmallVec;
+
+use crate::types::{decode_elements, decode_elements_strict_into, Elements};
+use crate::{AbiType, DecodeStatic, IntoOwned};
+
+impl<T, const N: usize> Elements<T> for SmallVec<[T; N]> {
+    fn reserve_elements(&mut self, len: usize) -> Result<(), ()> {
+        self.reserve(len);
+        Ok(())
+    }
+    fn push_element(&mut self, item: T) {
+        self.push(item);
+    }
+}
+
+/// `T[]`, spills to the heap past `N` elements
+impl<'a, T: DecodeStatic<'a>, const N: usize> DecodeStatic<'a> for SmallVec<[T; N]> {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let mut items = SmallVec::new();
+        decode_elements(&mut items, buf, len_offset)?;
+        Ok(items)
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let mut items = SmallVec::new();
+        let end = decode_elements_strict_into(&mut items, buf, len_offset)?;
+        Ok((items, end))
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], len_offset: usize) -> Result<(), ()> {
+        self.clear();
+        decode_elements(self, buf, len_offset)
+    }
+}
+
+impl<T: AbiType, const N: usize> AbiType for SmallVec<[T; N]> {
+    fn write_abi_type<W: Write>(f: &mut W) -> fmt::Result {
+        <Vec<T>>::write_abi_type(f)
+    }
+}
+
+impl<T: IntoOwned, const N: usize> IntoOwned for SmallVec<[T; N]> {
+    type Owned = SmallVec<[T::Owned; N]>;
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
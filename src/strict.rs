@@ -0,0 +1,69 @@
+//! Helpers for strict decoding, rejecting anything but the canonical encoding
+use crate::{slice_as_array, types::as_usize};
+
+/// The word at `offset`, `Err` if it is out of bounds or any of its first `high` bytes are set
+pub(crate) fn clean_word(buf: &[u8], offset: usize, high: usize) -> Result<&[u8; 32], ()> {
+    let word: &[u8; 32] = slice_as_array(
+        buf.get(offset..offset.checked_add(32).ok_or(())?)
+            .ok_or(())?,
+    );
+    if word[..high].iter().any(|b| *b != 0) {
+        return Err(());
+    }
+    Ok(word)
+}
+
+/// `Err` unless `bytes` is all zeroes
+pub(crate) fn zero_padded(bytes: &[u8]) -> Result<(), ()> {
+    if bytes.iter().any(|b| *b != 0) {
+        return Err(());
+    }
+    Ok(())
+}
+
+/// The offset or length at `offset`, `Err` if it doesn't fit a `usize`
+pub(crate) fn strict_usize(buf: &[u8], offset: usize) -> Result<usize, ()> {
+    let word = clean_word(buf, offset, 24)?;
+    usize::try_from(u64::from_be_bytes(*slice_as_array(&word[24..]))).map_err(|_| ())
+}
+
+/// Tracks where the previous tail ended so offsets must be in order and may not overlap
+#[doc(hidden)]
+pub struct TailCursor {
+    end: usize,
+}
+
+impl TailCursor {
+    /// Tails may start after the `head_size` bytes of heads
+    pub fn new(head_size: usize) -> Self {
+        Self { end: head_size }
+    }
+
+    /// Read the offset at `head_offset`, it must be word aligned and at or beyond the previous tail's end
+    pub fn next(&mut self, buf: &[u8], head_offset: usize) -> Result<usize, ()> {
+        let offset = strict_usize(buf, head_offset)?;
+        if offset % 32 != 0 || offset < self.end {
+            return Err(());
+        }
+        Ok(offset)
+    }
+
+    /// The current tail ends at `end`
+    pub fn end(&mut self, end: usize) {
+        self.end = end;
+    }
+
+    /// Where the last tail ended, or the heads if there were no tails
+    pub fn tail_end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Narrow `head_end`, where a tuple's head ends relative to its start, to the tail offset in the
+/// dynamic value's head at `head` as tails follow the head
+pub fn narrow_head_end(buf: &[u8], head: usize, head_end: usize) -> usize {
+    match buf.get(head..head + 32) {
+        Some(word) => head_end.min(as_usize(word)),
+        None => head_end,
+    }
+}
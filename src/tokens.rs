@@ -0,0 +1,341 @@
+//! Conversions between the static types and dynamic `ethabi::Token`s
+use alloc::vec::Vec;
+
+use ethabi::Token;
+use ethereum_types::{H160, U256};
+
+use crate::{
+    AddressZcp, Array, BytesZcp, DecodeStatic, FixedBytesZcp, StrZcp, Tuple, Tuples, Word, Wrapped,
+    WrappedCall, WrappedTuple,
+};
+
+/// Convert a decoded value into an `ethabi::Token`
+pub trait IntoToken {
+    fn into_token(self) -> Token;
+}
+
+/// Convert a (derived) struct into the tokens of its fields
+pub trait IntoTokens {
+    fn into_tokens(self) -> Vec<Token>;
+}
+
+/// Borrow a zero-copy value from an `ethabi::Token`
+pub trait FromToken<'a>: Sized {
+    fn from_token(token: &'a Token) -> Result<Self, ()>;
+}
+
+/// Borrow a (derived) struct from the tokens of its fields
+pub trait FromTokens<'a>: Sized {
+    fn from_tokens(tokens: &'a [Token]) -> Result<Self, ()>;
+}
+
+impl IntoToken for bool {
+    fn into_token(self) -> Token {
+        Token::Bool(self)
+    }
+}
+
+impl<'a> FromToken<'a> for bool {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Bool(b) => Ok(*b),
+            _ => Err(()),
+        }
+    }
+}
+
+impl IntoToken for U256 {
+    fn into_token(self) -> Token {
+        Token::Uint(self)
+    }
+}
+
+impl<'a> FromToken<'a> for U256 {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Uint(u) => Ok(*u),
+            _ => Err(()),
+        }
+    }
+}
+
+macro_rules! impl_uint_token {
+    ($($t:ty),*) => {
+        $(
+            impl IntoToken for $t {
+                fn into_token(self) -> Token {
+                    Token::Uint(U256::from(self))
+                }
+            }
+
+            impl<'a> FromToken<'a> for $t {
+                fn from_token(token: &'a Token) -> Result<Self, ()> {
+                    match token {
+                        Token::Uint(u) => <$t>::try_from(*u).map_err(|_| ()),
+                        _ => Err(()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_uint_token!(u8, u16, u32, u64, u128);
+
+impl IntoToken for AddressZcp<'_> {
+    fn into_token(self) -> Token {
+        Token::Address(H160(*self.0))
+    }
+}
+
+impl<'a> FromToken<'a> for AddressZcp<'a> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Address(a) => Ok(AddressZcp(a.as_fixed_bytes())),
+            _ => Err(()),
+        }
+    }
+}
+
+impl IntoToken for BytesZcp<'_> {
+    fn into_token(self) -> Token {
+        Token::Bytes(self.0.into())
+    }
+}
+
+impl<'a> FromToken<'a> for BytesZcp<'a> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Bytes(b) => Ok(BytesZcp(b)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl IntoToken for StrZcp<'_> {
+    fn into_token(self) -> Token {
+        Token::String(self.0.into())
+    }
+}
+
+impl<'a> FromToken<'a> for StrZcp<'a> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::String(s) => Ok(StrZcp(s)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<const N: usize> IntoToken for FixedBytesZcp<'_, N> {
+    fn into_token(self) -> Token {
+        Token::FixedBytes(self.0.into())
+    }
+}
+
+impl<'a, const N: usize> FromToken<'a> for FixedBytesZcp<'a, N> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::FixedBytes(b) => Ok(FixedBytesZcp(b.as_slice().try_into().map_err(|_| ())?)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl IntoToken for Word {
+    fn into_token(self) -> Token {
+        Token::FixedBytes(self.0.into())
+    }
+}
+
+impl<'a> FromToken<'a> for Word {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::FixedBytes(b) => Ok(Word(b.as_slice().try_into().map_err(|_| ())?)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<T: IntoToken, const N: usize> IntoToken for [T; N] {
+    fn into_token(self) -> Token {
+        Token::FixedArray(self.into_iter().map(IntoToken::into_token).collect())
+    }
+}
+
+impl<'a, T: FromToken<'a>, const N: usize> FromToken<'a> for [T; N] {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::FixedArray(items) => items
+                .iter()
+                .map(T::from_token)
+                .collect::<Result<Vec<T>, ()>>()?
+                .try_into()
+                .map_err(|_| ()),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<T: IntoToken> IntoToken for Vec<T> {
+    fn into_token(self) -> Token {
+        Token::Array(self.into_iter().map(IntoToken::into_token).collect())
+    }
+}
+
+impl<'a, T: FromToken<'a>> FromToken<'a> for Vec<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Array(items) => items.iter().map(T::from_token).collect(),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<T: IntoToken> IntoToken for Array<T> {
+    fn into_token(self) -> Token {
+        self.0.into_token()
+    }
+}
+
+impl<'a, T: FromToken<'a>> FromToken<'a> for Array<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        Ok(Self(Vec::from_token(token)?))
+    }
+}
+
+impl<T: IntoTokens> IntoToken for Tuple<T> {
+    fn into_token(self) -> Token {
+        Token::Tuple(self.0.into_tokens())
+    }
+}
+
+impl<'a, T: FromTokens<'a>> FromToken<'a> for Tuple<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Tuple(items) => Ok(Self(T::from_tokens(items)?)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<T: IntoTokens> IntoToken for Tuples<T> {
+    fn into_token(self) -> Token {
+        Token::Array(
+            self.0
+                .into_iter()
+                .map(|t| Token::Tuple(t.into_tokens()))
+                .collect(),
+        )
+    }
+}
+
+impl<'a, T: FromTokens<'a>> FromToken<'a> for Tuples<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        let tuples: Vec<Tuple<T>> = Vec::from_token(token)?;
+        Ok(Self(tuples.into_iter().map(|t| t.0).collect()))
+    }
+}
+
+/// `T` is ABI encoded back into `bytes`
+impl<T: IntoTokens> IntoToken for Wrapped<T> {
+    fn into_token(self) -> Token {
+        Token::Bytes(ethabi::encode(&self.0.into_tokens()))
+    }
+}
+
+impl<'a, T: DecodeStatic<'a>> FromToken<'a> for Wrapped<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Bytes(b) => Ok(Self(T::decode(b)?)),
+            _ => Err(()),
+        }
+    }
+}
+
+/// `T` is ABI encoded back into `bytes` as a dynamic tuple
+impl<T: IntoTokens> IntoToken for WrappedTuple<T> {
+    fn into_token(self) -> Token {
+        Token::Bytes(ethabi::encode(&[Token::Tuple(self.0.into_tokens())]))
+    }
+}
+
+impl<'a, T: DecodeStatic<'a>> FromToken<'a> for WrappedTuple<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Bytes(b) => Ok(Self(Tuple::<T>::decode(b)?.0)),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The selector followed by the ABI encoded args
+impl<T: IntoTokens> IntoToken for WrappedCall<T> {
+    fn into_token(self) -> Token {
+        let mut calldata = self.selector.to_vec();
+        calldata.extend(ethabi::encode(&self.args.into_tokens()));
+        Token::Bytes(calldata)
+    }
+}
+
+impl<'a, T: DecodeStatic<'a>> FromToken<'a> for WrappedCall<T> {
+    fn from_token(token: &'a Token) -> Result<Self, ()> {
+        match token {
+            Token::Bytes(b) if b.len() >= 4 => Ok(Self {
+                selector: b[..4].try_into().map_err(|_| ())?,
+                args: T::decode(&b[4..])?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+macro_rules! impl_into_token {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl From<$t> for Token {
+                fn from(value: $t) -> Self {
+                    value.into_token()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_token!(AddressZcp<'_>, BytesZcp<'_>);
+
+impl<const N: usize> From<FixedBytesZcp<'_, N>> for Token {
+    fn from(value: FixedBytesZcp<'_, N>) -> Self {
+        value.into_token()
+    }
+}
+
+impl<T: IntoTokens> From<Tuple<T>> for Token {
+    fn from(value: Tuple<T>) -> Self {
+        value.into_token()
+    }
+}
+
+impl<T: IntoTokens> From<Tuples<T>> for Token {
+    fn from(value: Tuples<T>) -> Self {
+        value.into_token()
+    }
+}
+
+impl<T: IntoTokens> From<Wrapped<T>> for Token {
+    fn from(value: Wrapped<T>) -> Self {
+        value.into_token()
+    }
+}
+
+impl<T: IntoTokens> From<WrappedTuple<T>> for Token {
+    fn from(value: WrappedTuple<T>) -> Self {
+        value.into_token()
+    }
+}
+
+impl<T: IntoTokens> From<WrappedCall<T>> for Token {
+    fn from(value: WrappedCall<T>) -> Self {
+        value.into_token()
+    }
+}
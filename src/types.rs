@@ -1,19 +1,156 @@
 //! Ethereum ABI static types and impls
-use ethereum_types::U256;
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::{fmt, ops::Deref, slice};
+#[cfg(feature = "alloc")]
+use core::{
+    ops::{DerefMut, Index},
+    slice::SliceIndex,
+};
+
+use ethereum_types::{H160, U256};
+
+use crate::strict::strict_usize;
+use crate::{
+    hex::{checksum, Hex},
+    strict::{clean_word, zero_padded, TailCursor},
+};
 
 /// Provides statically generated Eth ABI decode implementation
+///
+/// `offset` is always where the value itself is encoded: its head for static types, or its tail for
+/// dynamic types (`DYNAMIC`) e.g. the length word of a `bytes`. Offsets read from heads are
+/// relative to the start of the enclosing tuple or array body, `decode_from_head` follows them
+/// given that `base`
 pub trait DecodeStatic<'a>: Sized {
+    /// Whether the value is encoded in a tail behind an offset word in its head
+    const DYNAMIC: bool = false;
+    /// Size of the value's head i.e. its encoded size if static, or the 32 byte offset if dynamic
+    const HEAD_SIZE: usize = 32;
     /// Decode an instance from the given abi encoded buf starting at offset
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()>;
-    /// Decode an instance from eth abi buffer
+    /// Decode the value whose head is at `head_offset` in a tuple or array body starting at `base`,
+    /// following the head's offset (relative to `base`) if the value is dynamic
+    fn decode_from_head(buf: &'a [u8], base: usize, head_offset: usize) -> Result<Self, ()> {
+        if Self::DYNAMIC {
+            let head = buf.get(head_offset..head_offset + 32).ok_or(())?;
+            Self::decode_static(buf, base + as_usize(head))
+        } else {
+            Self::decode_static(buf, head_offset)
+        }
+    }
+    /// Decode an instance from eth abi buffer i.e. as the first value of the encoded tuple
     fn decode(buf: &'a [u8]) -> Result<Self, ()> {
-        Self::decode_static(buf, 0_usize)
+        Self::decode_from_head(buf, 0, 0)
+    }
+    /// Decode like `decode_static` but reject non-canonical encodings i.e. dirty high bits or padding,
+    /// bools other than 0/1 and out of order or overlapping offsets
+    ///
+    /// Defaults to `decode_static` for types with nothing further to check
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Self::decode_static(buf, offset)
+    }
+    /// Strict `decode_from_head`, the head's offset must be word aligned, canonical and past the head
+    fn decode_from_head_strict(buf: &'a [u8], base: usize, head_offset: usize) -> Result<Self, ()> {
+        if Self::DYNAMIC {
+            // the tail may not overlap the head it is found from
+            let head_end = head_offset.saturating_sub(base) + Self::HEAD_SIZE;
+            let offset = TailCursor::new(head_end).next(buf, head_offset)?;
+            Self::decode_static_strict(buf, base.checked_add(offset).ok_or(())?)
+        } else {
+            Self::decode_static_strict(buf, head_offset)
+        }
+    }
+    /// Strictly decode an instance from eth abi buffer, see `decode_static_strict`
+    fn decode_strict(buf: &'a [u8]) -> Result<Self, ()> {
+        Self::decode_from_head_strict(buf, 0, 0)
+    }
+    /// `decode_static_strict` also giving the end of the value's encoding i.e. of its tail if
+    /// dynamic, so the next tail may not overlap it
+    ///
+    /// Defaults to the end of the head, dynamic types should override it
+    fn decode_tail_strict(buf: &'a [u8], offset: usize) -> Result<(Self, usize), ()> {
+        Ok((
+            Self::decode_static_strict(buf, offset)?,
+            offset + Self::HEAD_SIZE,
+        ))
+    }
+    /// `decode_static` into `self`, reusing its allocations e.g. the capacity of `Vec`s
+    ///
+    /// Defaults to replacing `self`. On `Err` `self` may be partly decoded
+    fn decode_static_into(&mut self, buf: &'a [u8], offset: usize) -> Result<(), ()> {
+        *self = Self::decode_static(buf, offset)?;
+        Ok(())
+    }
+    /// `decode_from_head` into `self`, see `decode_static_into`
+    fn decode_from_head_into(
+        &mut self,
+        buf: &'a [u8],
+        base: usize,
+        head_offset: usize,
+    ) -> Result<(), ()> {
+        if Self::DYNAMIC {
+            let head = buf.get(head_offset..head_offset + 32).ok_or(())?;
+            self.decode_static_into(buf, base + as_usize(head))
+        } else {
+            self.decode_static_into(buf, head_offset)
+        }
+    }
+    /// `decode` into `self`, reusing its allocations when decoding the same shape repeatedly
+    fn decode_into(&mut self, buf: &'a [u8]) -> Result<(), ()> {
+        self.decode_from_head_into(buf, 0, 0)
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// address
+///
+/// `Display` is EIP-55 checksummed, `LowerHex` is plain hex (`0x` prefixed with `{:#x}`), see
+/// `parse_checksummed` for the reverse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct AddressZcp<'a>(pub &'a [u8; 20]);
 
+impl fmt::Display for AddressZcp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = checksum(self.0);
+        // checksum output is always ASCII
+        f.write_str(core::str::from_utf8(&hex).expect("ascii hex"))
+    }
+}
+
+impl fmt::LowerHex for AddressZcp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        write!(f, "{}", Hex(self.0))
+    }
+}
+
+impl PartialEq<H160> for AddressZcp<'_> {
+    fn eq(&self, other: &H160) -> bool {
+        self.0 == other.as_fixed_bytes()
+    }
+}
+
+impl PartialEq<AddressZcp<'_>> for H160 {
+    fn eq(&self, other: &AddressZcp<'_>) -> bool {
+        self.as_fixed_bytes() == other.0
+    }
+}
+
+impl Default for AddressZcp<'_> {
+    fn default() -> Self {
+        Self(&[0; 20])
+    }
+}
+
+impl Deref for AddressZcp<'_> {
+    type Target = [u8; 20];
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
 impl<'a> AsRef<[u8; 20]> for AddressZcp<'a> {
     fn as_ref(&self) -> &'a [u8; 20] {
         self.0
@@ -27,15 +164,53 @@ impl<'a> AddressZcp<'a> {
 }
 
 /// yet another borrowed bytes type...
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct BytesZcp<'a>(pub &'a [u8]);
 
+impl Deref for BytesZcp<'_> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a> IntoIterator for BytesZcp<'a> {
+    type Item = &'a u8;
+    type IntoIter = slice::Iter<'a, u8>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl<'a> AsRef<[u8]> for BytesZcp<'a> {
     fn as_ref(&self) -> &[u8] {
         self.0
     }
 }
 
+/// string, validated as UTF-8 when decoded
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StrZcp<'a>(pub &'a str);
+
+impl Deref for StrZcp<'_> {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a> AsRef<str> for StrZcp<'a> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for StrZcp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
 /// bytes32
 pub type Bytes32<'a> = FixedBytesZcp<'a, 32>;
 /// bytes16
@@ -46,181 +221,525 @@ pub type Bytes8<'a> = FixedBytesZcp<'a, 8>;
 pub type Bytes4<'a> = FixedBytesZcp<'a, 4>;
 
 /// bytesN
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct FixedBytesZcp<'a, const N: usize>(pub &'a [u8; N]);
 
+impl<const N: usize> Default for FixedBytesZcp<'_, N> {
+    fn default() -> Self {
+        Self(&[0; N])
+    }
+}
+
+impl<const N: usize> Deref for FixedBytesZcp<'_, N> {
+    type Target = [u8; N];
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for FixedBytesZcp<'a, N> {
+    type Item = &'a u8;
+    type IntoIter = slice::Iter<'a, u8>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl<'a, const N: usize> FixedBytesZcp<'a, N> {
     fn new(val: &'a [u8]) -> Self {
         Self(slice_as_array(val))
     }
 }
 
-/// T[N]
-#[derive(Debug)]
-pub struct FixedArrayZcp<'a, const N: usize, T>(&'a [T; N]);
+/// T[N] borrowed from the input, decodable for `WordAligned` element types e.g. `FixedArrayZcp<'a, 4, Word>`
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FixedArrayZcp<'a, const N: usize, T>(pub &'a [T; N]);
+
+impl<'a, const N: usize, T> FixedArrayZcp<'a, N, T> {
+    pub const fn new(array: &'a [T; N]) -> Self {
+        Self(array)
+    }
+}
+
+impl<const N: usize, T> Clone for FixedArrayZcp<'_, N, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize, T> Copy for FixedArrayZcp<'_, N, T> {}
+
+impl<const N: usize, T> Deref for FixedArrayZcp<'_, N, T> {
+    type Target = [T; N];
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, const N: usize, T> IntoIterator for FixedArrayZcp<'a, N, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// A raw ABI word, e.g. a `bytes32` or `uint256` left as big endian bytes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Word(pub [u8; 32]);
+
+impl From<Word> for U256 {
+    fn from(word: Word) -> Self {
+        U256::from_big_endian(&word.0)
+    }
+}
+
+impl<'a> DecodeStatic<'a> for Word {
+    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(Word(*slice_as_array(
+            buf.get(offset..offset + 32).ok_or(())?,
+        )))
+    }
+}
+
+/// Types laid out exactly as their ABI encoding so they can be borrowed straight from the input
+///
+/// # Safety
+/// Implementors must have alignment 1, a size that is a multiple of 32 and be valid for any bytes
+pub unsafe trait WordAligned {}
+
+unsafe impl WordAligned for Word {}
+unsafe impl<T: WordAligned, const N: usize> WordAligned for [T; N] {}
 
 /// Cast &[T] to &[T; N] w/out runtime checks
-fn slice_as_array<T, const N: usize>(slice: &[T]) -> &[T; N] {
+pub(crate) fn slice_as_array<T, const N: usize>(slice: &[T]) -> &[T; N] {
     unsafe { &*(slice as *const [T] as *const [T; N]) }
 }
 
 impl<'a> DecodeStatic<'a> for AddressZcp<'a> {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = AddressZcp::new(&buf[offset + 12..offset + 32]);
-        Ok(result)
+        Ok(AddressZcp::new(&clean_word(buf, offset, 0)?[12..]))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(AddressZcp::new(&clean_word(buf, offset, 12)?[12..]))
     }
 }
 
 impl<'a> DecodeStatic<'a> for bool {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        Ok(buf[offset + 31] == 1)
+        Ok(clean_word(buf, offset, 0)?[31] == 1)
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        match clean_word(buf, offset, 31)?[31] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(()),
+        }
     }
 }
 
 impl<'a> DecodeStatic<'a> for U256 {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = U256::from(slice_as_array(unsafe {
-            buf.get_unchecked(offset..offset + 32_usize)
-        }));
-        Ok(result)
+        Ok(U256::from(clean_word(buf, offset, 0)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(U256::from(clean_word(buf, offset, 0)?))
     }
 }
 
 impl<'a> DecodeStatic<'a> for u128 {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = u128::from_be_bytes(*slice_as_array(unsafe {
-            buf.get_unchecked(offset + 16..offset + 32_usize)
-        }));
-        Ok(result)
+        Ok(u128::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 0)?[16..],
+        )))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(u128::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 16)?[16..],
+        )))
     }
 }
 
 impl<'a> DecodeStatic<'a> for u64 {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = u64::from_be_bytes(*slice_as_array(unsafe {
-            buf.get_unchecked(offset + 24..offset + 32_usize)
-        }));
-        Ok(result)
+        Ok(u64::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 0)?[24..],
+        )))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(u64::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 24)?[24..],
+        )))
     }
 }
 
 impl<'a> DecodeStatic<'a> for u32 {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = u32::from_be_bytes(*slice_as_array(unsafe {
-            buf.get_unchecked(offset + 28..offset + 32_usize)
-        }));
-        Ok(result)
+        Ok(u32::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 0)?[28..],
+        )))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(u32::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 28)?[28..],
+        )))
     }
 }
 
 impl<'a> DecodeStatic<'a> for u16 {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = u16::from_be_bytes(*slice_as_array(unsafe {
-            buf.get_unchecked(offset + 30..offset + 32_usize)
-        }));
-        Ok(result)
+        Ok(u16::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 0)?[30..],
+        )))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(u16::from_be_bytes(*slice_as_array(
+            &clean_word(buf, offset, 30)?[30..],
+        )))
     }
 }
 
 impl<'a> DecodeStatic<'a> for u8 {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        Ok(buf[offset + 31])
+        Ok(clean_word(buf, offset, 0)?[31])
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(clean_word(buf, offset, 31)?[31])
     }
 }
 
 impl<'a> DecodeStatic<'a> for BytesZcp<'a> {
+    const DYNAMIC: bool = true;
     fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
         let data_offset = len_offset + 32;
-        let len = as_usize(&buf[len_offset..]);
-        let result = BytesZcp(&buf[data_offset..data_offset + len]);
+        let len = as_usize(buf.get(len_offset..data_offset).ok_or(())?);
+        let result = BytesZcp(buf.get(data_offset..data_offset + len).ok_or(())?);
         Ok(result)
     }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    /// The payload must be zero padded to a whole word
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let len = strict_usize(buf, len_offset)?;
+        let data_offset = len_offset + 32;
+        let end = len
+            .checked_next_multiple_of(32)
+            .and_then(|padded| data_offset.checked_add(padded))
+            .ok_or(())?;
+        let padded = buf.get(data_offset..end).ok_or(())?;
+        zero_padded(&padded[len..])?;
+        Ok((BytesZcp(&padded[..len]), end))
+    }
+}
+
+/// Invalid UTF-8 is an error
+impl<'a> DecodeStatic<'a> for StrZcp<'a> {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let bytes = BytesZcp::decode_static(buf, len_offset)?;
+        Ok(StrZcp(core::str::from_utf8(bytes.0).map_err(|_| ())?))
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (bytes, end) = BytesZcp::decode_tail_strict(buf, len_offset)?;
+        Ok((StrZcp(core::str::from_utf8(bytes.0).map_err(|_| ())?), end))
+    }
 }
 
 /// An array of dynamic tuples
-#[derive(Debug, PartialEq)]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Tuples<T>(pub Vec<T>);
 
+#[cfg(feature = "alloc")]
 impl<T> From<Vec<T>> for Tuples<T> {
     fn from(v: Vec<T>) -> Self {
         Self(v)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T> DecodeStatic<'a> for Tuples<T>
 where
     T: DecodeStatic<'a>,
 {
+    const DYNAMIC: bool = true;
     /// Assumes array of tuples
-    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let len_offset = as_usize(&buf[offset..]);
-        let len: usize = as_usize(&buf[len_offset..]);
-        let tail_offset = len_offset + 32;
-
-        Ok((0..len)
-            .map(|i| {
-                let next_tail_offset = tail_offset + i * 32;
-                // the tail offsets don't include the outer header hence +shift
-                as_usize(unsafe { buf.get_unchecked(next_tail_offset..) }) + tail_offset
-            })
-            .map(|o| T::decode(unsafe { buf.get_unchecked(o..) }).unwrap())
-            .collect::<Vec<T>>()
-            .into())
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let len = as_usize(buf.get(len_offset..len_offset + 32).ok_or(())?);
+        // the element offsets are relative to the first element head
+        let body = len_offset + 32;
+        let mut items = Vec::with_capacity(len.min(buf.len() / 32));
+        for i in 0..len {
+            items.push(Tuple::<T>::decode_from_head(buf, body, body + i * 32)?.0);
+        }
+        Ok(Self(items))
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (items, end) = decode_elements_strict::<Tuple<T>>(buf, len_offset)?;
+        Ok((Self(items.into_iter().map(|item| item.0).collect()), end))
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], len_offset: usize) -> Result<(), ()> {
+        decode_elements_into(&mut self.0, buf, len_offset, true)
     }
 }
 
 /// helper to decode `T` as a dynamic tuple (default behaviour of `T` as a static tuple)
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Tuple<T>(pub T);
 
 // dynamic tuple
 impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Tuple<T> {
+    const DYNAMIC: bool = true;
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let tail_offset = as_usize(&buf[offset..]);
-        Ok(Self(T::decode(&buf[tail_offset..]).unwrap()))
+        Ok(Self(T::decode_static(buf, offset)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(Self(T::decode_static_strict(buf, offset)?))
+    }
+    fn decode_tail_strict(buf: &'a [u8], offset: usize) -> Result<(Self, usize), ()> {
+        let (value, end) = T::decode_tail_strict(buf, offset)?;
+        Ok((Self(value), end))
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], offset: usize) -> Result<(), ()> {
+        self.0.decode_static_into(buf, offset)
     }
 }
 
-pub struct Array<T, const D: bool>(pub Vec<T>);
+/// `T[]`, elements are behind offsets if `T` is dynamic
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Array<T>(pub Vec<T>);
+
+/// `Deref`, `IntoIterator`, `Index` and `Default` for the `Vec` backed types
+macro_rules! impl_vec_wrapper {
+    ($t:ident) => {
+        #[cfg(feature = "alloc")]
+        impl<T> Default for $t<T> {
+            fn default() -> Self {
+                Self(Vec::new())
+            }
+        }
 
-impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Array<T, true> {
-    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
-        let len = as_usize(&buf[len_offset..]);
-        let tail_offset = len_offset + 32;
-        let mut items = Vec::with_capacity(len);
-
-        (0..len)
-            .map(|i| {
-                let next_tail_offset = tail_offset + i * 32;
-                // the tail offsets don't include the length word hence +32
-                as_usize(unsafe { buf.get_unchecked(next_tail_offset..) }) + tail_offset
-            })
-            .for_each(|o| {
-                items.push(T::decode(&buf[o..]).unwrap());
-            });
+        #[cfg(feature = "alloc")]
+        impl<T> Deref for $t<T> {
+            type Target = Vec<T>;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> DerefMut for $t<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T, I: SliceIndex<[T]>> Index<I> for $t<T> {
+            type Output = I::Output;
+            fn index(&self, index: I) -> &Self::Output {
+                &self.0[index]
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> IntoIterator for $t<T> {
+            type Item = T;
+            type IntoIter = vec::IntoIter<T>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'a, T> IntoIterator for &'a $t<T> {
+            type Item = &'a T;
+            type IntoIter = slice::Iter<'a, T>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+    };
+}
 
+impl_vec_wrapper!(Tuples);
+impl_vec_wrapper!(Array);
+
+#[cfg(feature = "alloc")]
+impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Array<T> {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let len = as_usize(buf.get(len_offset..len_offset + 32).ok_or(())?);
+        // element offsets are relative to the first element head
+        let body = len_offset + 32;
+        let mut items = Vec::with_capacity(len.min(buf.len() / 32));
+        for i in 0..len {
+            items.push(T::decode_from_head(buf, body, body + i * T::HEAD_SIZE)?);
+        }
         Ok(Self(items))
     }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self(decode_elements_strict(buf, len_offset)?.0))
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (items, end) = decode_elements_strict(buf, len_offset)?;
+        Ok((Self(items), end))
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], len_offset: usize) -> Result<(), ()> {
+        decode_elements_into(&mut self.0, buf, len_offset, false)
+    }
 }
 
-impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Array<T, false> {
+/// `T[]`, see `Array`
+#[cfg(feature = "alloc")]
+impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Vec<T> {
+    const DYNAMIC: bool = true;
     fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
-        let len = as_usize(&buf[len_offset..]);
-        let mut items = Vec::with_capacity(len);
-        (0..len).for_each(|i| {
-            // the tail offsets don't include the length word hence +32
-            let idx = len_offset + 32 + i * 32;
-            items.push(DecodeStatic::decode(&buf[idx..]).unwrap());
-        });
+        Ok(Array::<T>::decode_static(buf, len_offset)?.0)
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(decode_elements_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        decode_elements_strict(buf, len_offset)
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], len_offset: usize) -> Result<(), ()> {
+        decode_elements_into(self, buf, len_offset, false)
+    }
+}
 
-        Ok(Self(items))
+/// Decode the elements of an array at `len_offset` into `items`, decoding into the existing
+/// elements and keeping the `Vec`'s capacity
+///
+/// `tuples` if each element is a dynamic tuple i.e. behind an offset regardless of `T::DYNAMIC`
+#[cfg(feature = "alloc")]
+fn decode_elements_into<'a, T: DecodeStatic<'a>>(
+    items: &mut Vec<T>,
+    buf: &'a [u8],
+    len_offset: usize,
+    tuples: bool,
+) -> Result<(), ()> {
+    let len = as_usize(buf.get(len_offset..len_offset + 32).ok_or(())?);
+    // the element offsets are relative to the first element head
+    let body = len_offset + 32;
+    items.truncate(len);
+    items.reserve(len.min(buf.len() / 32).saturating_sub(items.len()));
+    for i in 0..len {
+        let item = items.get_mut(i);
+        if tuples {
+            let head = body + i * 32;
+            let offset = body + as_usize(buf.get(head..head + 32).ok_or(())?);
+            match item {
+                Some(item) => item.decode_static_into(buf, offset)?,
+                None => items.push(T::decode_static(buf, offset)?),
+            }
+        } else {
+            let head = body + i * T::HEAD_SIZE;
+            match item {
+                Some(item) => item.decode_from_head_into(buf, body, head)?,
+                None => items.push(T::decode_from_head(buf, body, head)?),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Strictly decode the elements of an array at `len_offset`, giving them and the end of the array
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_elements_strict<'a, T: DecodeStatic<'a>>(
+    buf: &'a [u8],
+    len_offset: usize,
+) -> Result<(Vec<T>, usize), ()> {
+    let mut items = Vec::new();
+    let end = decode_elements_strict_into(&mut items, buf, len_offset)?;
+    Ok((items, end))
+}
+
+/// A sequence the elements of an array are decoded into
+#[cfg(any(feature = "alloc", feature = "arrayvec"))]
+pub(crate) trait Elements<T> {
+    /// Make room for `len` more elements, `Err` if they don't fit
+    fn reserve_elements(&mut self, len: usize) -> Result<(), ()>;
+    fn push_element(&mut self, item: T);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Elements<T> for Vec<T> {
+    fn reserve_elements(&mut self, len: usize) -> Result<(), ()> {
+        self.reserve(len);
+        Ok(())
+    }
+    fn push_element(&mut self, item: T) {
+        self.push(item);
+    }
+}
+
+/// Decode the elements of an array at `len_offset`, appending them to `items`
+#[cfg(any(feature = "smallvec", feature = "arrayvec"))]
+pub(crate) fn decode_elements<'a, T: DecodeStatic<'a>>(
+    items: &mut impl Elements<T>,
+    buf: &'a [u8],
+    len_offset: usize,
+) -> Result<(), ()> {
+    let len = as_usize(buf.get(len_offset..len_offset + 32).ok_or(())?);
+    // element offsets are relative to the first element head
+    let body = len_offset + 32;
+    // the length is untrusted, each element takes at least a word
+    items.reserve_elements(len.min(buf.len() / 32))?;
+    for i in 0..len {
+        items.push_element(T::decode_from_head(buf, body, body + i * T::HEAD_SIZE)?);
+    }
+    Ok(())
+}
+
+/// Strictly decode the elements of an array at `len_offset`, appending them to `items` and giving
+/// the end of the array
+///
+/// Dynamic elements' tails must be in order and may not overlap
+#[cfg(any(feature = "alloc", feature = "arrayvec"))]
+pub(crate) fn decode_elements_strict_into<'a, T: DecodeStatic<'a>>(
+    items: &mut impl Elements<T>,
+    buf: &'a [u8],
+    len_offset: usize,
+) -> Result<usize, ()> {
+    let len = strict_usize(buf, len_offset)?;
+    let body = len_offset + 32;
+    let head_size = len.checked_mul(T::HEAD_SIZE).ok_or(())?;
+    if body.checked_add(head_size).ok_or(())? > buf.len() {
+        return Err(());
     }
+    items.reserve_elements(len)?;
+    if !T::DYNAMIC {
+        for i in 0..len {
+            items.push_element(T::decode_static_strict(buf, body + i * T::HEAD_SIZE)?);
+        }
+        return Ok(body + head_size);
+    }
+    let mut tails = TailCursor::new(head_size);
+    for i in 0..len {
+        let offset = tails.next(buf, body + i * 32)?;
+        let (item, end) = T::decode_tail_strict(buf, body + offset)?;
+        tails.end(end - body);
+        items.push_element(item);
+    }
+    Ok(body + tails.tail_end())
 }
 
 /// Helper type meaning a type encoded as `bytes` should be decoded as a `T`
 ///  E.g. the makerdao multicall contract returns ABI encoded results from proxy calls
 ///
-/// ``ignore
+/// The payload is `T` in place i.e. `abi.encode(a, b, ...)` of `T`'s fields, a static tuple or
+/// function return data, see `WrappedTuple` and `WrappedCall` for other payloads
+/// ```ignore
 ///     struct ContractResult<'a> {
 ///         a: BytesZcp<'a>,
 ///         b: AddressZcp<'a>,
@@ -230,66 +749,195 @@ impl<'a, T: DecodeStatic<'a>> DecodeStatic<'a> for Array<T, false> {
 ///         return_data: Wrapped<ContractResult<'a>>,
 ///     }
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Wrapped<T>(pub T);
 
+/// `bytes` holding a dynamic tuple `T` i.e. `abi.encode(t)` which starts with the offset of `t`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappedTuple<T>(pub T);
+
+/// `bytes` holding calldata i.e. a 4 byte selector then the ABI encoded args `T`
+///
+/// E.g. the calls of a multicall or a Safe transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappedCall<T> {
+    pub selector: [u8; 4],
+    pub args: T,
+}
+
+/// Fails to build wherever it is called for a dynamic `T`, generic so only uses are checked
+///
+/// E.g. a derived struct's `decode_exact` if a field is dynamic
+/// ```compile_fail,E0080
+/// use ethabi_static::{Array, DecodeStatic};
+/// use ethereum_types::U256;
+///
+/// #[derive(DecodeStatic)]
+/// struct WithArray {
+///     a: u8,
+///     b: Array<U256>,
+/// }
+///
+/// let _ = WithArray::decode_exact_slice(&[]);
+/// ```
+#[doc(hidden)]
+#[inline(always)]
+pub fn assert_static<'a, T: DecodeStatic<'a>>() {
+    const { assert!(!T::DYNAMIC, "only static structs can be decoded exactly") };
+}
+
+/// `Err` unless `T`'s head fits in `payload`
+pub(crate) fn check_head<'a, T: DecodeStatic<'a>>(payload: &[u8]) -> Result<(), ()> {
+    if payload.len() < T::HEAD_SIZE {
+        return Err(());
+    }
+    Ok(())
+}
+
 impl<'a, T> DecodeStatic<'a> for Wrapped<T>
 where
     T: DecodeStatic<'a>,
 {
+    const DYNAMIC: bool = true;
     fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
-        let data_offset = len_offset + 64; // = bytes offset + bytes length
-        let len = as_usize(&buf[len_offset..]);
-        Ok(Wrapped(T::decode(&buf[data_offset..data_offset + len])?))
+        let payload = BytesZcp::decode_static(buf, len_offset)?.0;
+        check_head::<T>(payload)?;
+        Ok(Wrapped(T::decode(payload)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (BytesZcp(payload), end) = BytesZcp::decode_tail_strict(buf, len_offset)?;
+        check_head::<T>(payload)?;
+        Ok((Wrapped(T::decode_strict(payload)?), end))
     }
 }
 
-impl<'a, T, const N: usize> DecodeStatic<'a> for [T; N]
+impl<'a, T> DecodeStatic<'a> for WrappedTuple<T>
 where
-    T: Default + DecodeStatic<'a>,
-    [T; N]: Default,
+    T: DecodeStatic<'a>,
 {
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let payload = BytesZcp::decode_static(buf, len_offset)?.0;
+        check_head::<Tuple<T>>(payload)?;
+        let offset = as_usize(payload);
+        check_head::<T>(payload.get(offset..).ok_or(())?)?;
+        Ok(WrappedTuple(T::decode_static(payload, offset)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (BytesZcp(payload), end) = BytesZcp::decode_tail_strict(buf, len_offset)?;
+        check_head::<Tuple<T>>(payload)?;
+        let offset = TailCursor::new(32).next(payload, 0)?;
+        check_head::<T>(payload.get(offset..).ok_or(())?)?;
+        Ok((WrappedTuple(T::decode_static_strict(payload, offset)?), end))
+    }
+}
+
+impl<'a, T> DecodeStatic<'a> for WrappedCall<T>
+where
+    T: DecodeStatic<'a>,
+{
+    const DYNAMIC: bool = true;
+    fn decode_static(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        let payload = BytesZcp::decode_static(buf, len_offset)?.0;
+        let (selector, args) = split_selector::<T>(payload)?;
+        Ok(WrappedCall {
+            selector,
+            args: T::decode(args)?,
+        })
+    }
+    fn decode_static_strict(buf: &'a [u8], len_offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, len_offset)?.0)
+    }
+    fn decode_tail_strict(buf: &'a [u8], len_offset: usize) -> Result<(Self, usize), ()> {
+        let (BytesZcp(payload), end) = BytesZcp::decode_tail_strict(buf, len_offset)?;
+        let (selector, args) = split_selector::<T>(payload)?;
+        let call = WrappedCall {
+            selector,
+            args: T::decode_strict(args)?,
+        };
+        Ok((call, end))
+    }
+}
+
+/// Split calldata into its selector and args, `Err` unless the args hold `T`'s head
+fn split_selector<'a, T: DecodeStatic<'a>>(payload: &'a [u8]) -> Result<([u8; 4], &'a [u8]), ()> {
+    if payload.len() < 4 {
+        return Err(());
+    }
+    let (selector, args) = payload.split_at(4);
+    check_head::<T>(args)?;
+    Ok((*slice_as_array(selector), args))
+}
+
+/// `T[N]`, dynamic if `T` is
+impl<'a, T: DecodeStatic<'a>, const N: usize> DecodeStatic<'a> for [T; N] {
+    const DYNAMIC: bool = T::DYNAMIC;
+    const HEAD_SIZE: usize = if T::DYNAMIC { 32 } else { N * T::HEAD_SIZE };
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        //let tail_offset = N * 32;
-        let tail = &buf[offset..];
-        let mut tokens: [T; N] = Default::default();
-        let mut new_offset = 0;
-        for idx in 0..N {
-            let res = T::decode_static(tail, new_offset)?;
-            new_offset += 32; // static only
-            tokens[idx] = res;
+        try_array(|i| T::decode_from_head(buf, offset, offset + i * T::HEAD_SIZE))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        Ok(Self::decode_tail_strict(buf, offset)?.0)
+    }
+    /// Dynamic elements' tails must be in order and may not overlap
+    fn decode_tail_strict(buf: &'a [u8], offset: usize) -> Result<(Self, usize), ()> {
+        if !T::DYNAMIC {
+            let items = try_array(|i| T::decode_static_strict(buf, offset + i * T::HEAD_SIZE))?;
+            return Ok((items, offset + Self::HEAD_SIZE));
+        }
+        let mut tails = TailCursor::new(N * 32);
+        let items = try_array(|i| {
+            let tail_offset = tails.next(buf, offset + i * 32)?;
+            let (item, end) = T::decode_tail_strict(buf, offset + tail_offset)?;
+            tails.end(end - offset);
+            Ok(item)
+        })?;
+        Ok((items, offset + tails.tail_end()))
+    }
+    fn decode_static_into(&mut self, buf: &'a [u8], offset: usize) -> Result<(), ()> {
+        for (i, item) in self.iter_mut().enumerate() {
+            item.decode_from_head_into(buf, offset, offset + i * T::HEAD_SIZE)?;
         }
-        Ok(tokens)
+        Ok(())
+    }
+}
+
+/// Build an array from a fallible fn of the index
+fn try_array<T, const N: usize>(mut f: impl FnMut(usize) -> Result<T, ()>) -> Result<[T; N], ()> {
+    let items: [Result<T, ()>; N] = core::array::from_fn(&mut f);
+    if items.iter().any(Result::is_err) {
+        return Err(());
+    }
+    Ok(items.map(|item| item.unwrap_or_else(|_| unreachable!())))
+}
+
+/// Borrows `N * size_of::<T>()` bytes in place, no copying or conversion
+impl<'a, const N: usize, T: WordAligned> DecodeStatic<'a> for FixedArrayZcp<'a, N, T> {
+    const HEAD_SIZE: usize = N * core::mem::size_of::<T>();
+    fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        let words = buf.get(offset..offset + Self::HEAD_SIZE).ok_or(())?;
+        // SAFETY: `T: WordAligned` is align 1 and valid for any bytes, the length is checked above
+        Ok(Self(unsafe { &*(words.as_ptr() as *const [T; N]) }))
     }
 }
 
 impl<'a, const N: usize> DecodeStatic<'a> for FixedBytesZcp<'a, N> {
     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-        let result = Self::new(&buf[offset..offset + 32]);
-        Ok(result)
+        Ok(Self::new(clean_word(buf, offset, 0)?))
+    }
+    fn decode_static_strict(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+        let word = clean_word(buf, offset, 0)?;
+        zero_padded(word.get(N..).ok_or(())?)?;
+        Ok(Self::new(word))
     }
 }
 
-// impl<'a, A> DecodeStatic<'a> for SmallVec<A>
-// where
-//     A: Array,
-//     <A as Array>::Item: DecodeStatic<'a>,
-// {
-//     fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
-//         let len_offset = as_usize(&buf[offset..offset + 32]);
-//         let len = as_usize(&buf[len_offset..len_offset + 32]);
-//         let tail_offset = len_offset + 32;
-//         let tail = &buf[tail_offset..];
-//         let mut tokens = SmallVec::with_capacity(len);
-//         let mut new_offset = 0;
-//         for _ in 0..len {
-//             let res = <A as Array>::Item::decode_static(tail, new_offset)?;
-//             new_offset += 32;
-//             tokens.push(res);
-//         }
-//         Ok(tokens)
-//     }
-// }
 pub(crate) fn as_usize(buf: &[u8]) -> usize {
     // OPTIMIZATION: nothing sensible should ever be longer than 2 ** 16 so we ignore the other bytes
     // ((unsafe { *buf.get_unchecked(28) } as usize) << 24)
@@ -0,0 +1,101 @@
+//! Uniswap V3 swap `path` decoding
+use crate::{slice_as_array, AddressZcp, DecodePacked};
+
+/// packed `address` + `uint24`
+const HOP_SIZE: usize = 20 + 3;
+
+/// A Uniswap V3 `path` i.e. `abi.encodePacked(token, fee, token, fee, ..., token)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct V3Path<'a>(&'a [u8]);
+
+impl<'a> V3Path<'a> {
+    /// `Err` unless `path` has at least one hop and no trailing bytes
+    pub fn new(path: &'a [u8]) -> Result<Self, ()> {
+        if path.len() < HOP_SIZE + 20 || !(path.len() - 20).is_multiple_of(HOP_SIZE) {
+            return Err(());
+        }
+        Ok(Self(path))
+    }
+
+    /// The encoded path
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Number of pools in the path
+    pub fn len(&self) -> usize {
+        (self.0.len() - 20) / HOP_SIZE
+    }
+
+    /// Always `false`, a valid path has at least one hop
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The first token in the path
+    pub fn token_in(&self) -> AddressZcp<'a> {
+        AddressZcp(slice_as_array(&self.0[..20]))
+    }
+
+    /// The last token in the path
+    pub fn token_out(&self) -> AddressZcp<'a> {
+        AddressZcp(slice_as_array(&self.0[self.0.len() - 20..]))
+    }
+
+    /// Iterate the hops, borrowing from the path
+    pub fn hops(&self) -> V3Hops<'a> {
+        V3Hops { rest: self.0 }
+    }
+}
+
+impl<'a> IntoIterator for V3Path<'a> {
+    type Item = V3Hop<'a>;
+    type IntoIter = V3Hops<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.hops()
+    }
+}
+
+/// Takes the rest of the input
+impl<'a> DecodePacked<'a> for V3Path<'a> {
+    fn decode_packed_prefix(buf: &'a [u8]) -> Result<(Self, &'a [u8]), ()> {
+        Ok((Self::new(buf)?, &[]))
+    }
+}
+
+/// One pool of a `V3Path`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct V3Hop<'a> {
+    pub token_in: AddressZcp<'a>,
+    /// Pool fee in hundredths of a bip i.e. `500` is 0.05%
+    pub fee: u32,
+    pub token_out: AddressZcp<'a>,
+}
+
+/// Iterator over the hops of a `V3Path`
+#[derive(Debug, Clone)]
+pub struct V3Hops<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for V3Hops<'a> {
+    type Item = V3Hop<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hop = self.rest.get(..HOP_SIZE + 20)?;
+        // the next hop starts at this hop's `token_out`
+        self.rest = &self.rest[HOP_SIZE..];
+        Some(V3Hop {
+            token_in: AddressZcp(slice_as_array(&hop[..20])),
+            fee: u32::from_be_bytes([0, hop[20], hop[21], hop[22]]),
+            token_out: AddressZcp(slice_as_array(&hop[HOP_SIZE..])),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len().saturating_sub(20) / HOP_SIZE;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for V3Hops<'_> {}
@@ -0,0 +1,83 @@
+#![cfg(test)]
+
+use std::collections::HashMap;
+
+use ethabi_static::{parse_checksummed, AddressZcp};
+use ethereum_types::H160;
+use hex_literal::hex;
+
+#[test]
+fn address_display_is_checksummed() {
+    // EIP-55 test vectors
+    for checksummed in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let address: H160 = checksummed.parse().unwrap();
+        let zcp = AddressZcp(address.as_fixed_bytes());
+        assert_eq!(zcp.to_string(), checksummed);
+        assert_eq!(format!("{:#x}", zcp), checksummed.to_lowercase());
+        assert_eq!(format!("{:x}", zcp), checksummed[2..].to_lowercase());
+    }
+}
+
+#[test]
+fn parse_checksummed_address() {
+    // EIP-55 test vectors
+    for checksummed in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let address: H160 = checksummed.parse().unwrap();
+        assert_eq!(parse_checksummed(checksummed), Ok(address));
+        // no checksum to check
+        let lower = checksummed.to_lowercase();
+        assert_eq!(parse_checksummed(&lower), Ok(address));
+        let upper = format!("0x{}", checksummed[2..].to_uppercase());
+        assert_eq!(parse_checksummed(&upper), Ok(address));
+        // one letter's case flipped
+        let idx = checksummed
+            .rfind(|c: char| c.is_ascii_alphabetic())
+            .unwrap();
+        let mut flipped = checksummed.to_string();
+        flipped.replace_range(idx..=idx, &checksummed[idx..=idx].to_lowercase());
+        if flipped == checksummed {
+            flipped.replace_range(idx..=idx, &checksummed[idx..=idx].to_uppercase());
+        }
+        assert_eq!(parse_checksummed(&flipped), Err(()));
+    }
+
+    assert_eq!(
+        parse_checksummed("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Err(())
+    );
+    assert_eq!(
+        parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+        Err(())
+    );
+    assert_eq!(
+        parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+        Err(())
+    );
+}
+
+#[test]
+fn address_compares_with_h160() {
+    let pool = hex!("88e6a0c2ddd26feeb64f039a2c41296fcb3f5640");
+    let other = hex!("8ad599c3a0ff1de082011efddc58f1908eb6e6d8");
+    let h160 = H160(pool);
+
+    assert_eq!(AddressZcp(&pool), h160);
+    assert_eq!(h160, AddressZcp(&pool));
+    assert_ne!(AddressZcp(&other), h160);
+    assert!(AddressZcp(&pool) < AddressZcp(&other));
+
+    let mut reserves = HashMap::new();
+    reserves.insert(AddressZcp(&pool), 1_u128);
+    reserves.insert(AddressZcp(&other), 2_u128);
+    assert_eq!(reserves[&AddressZcp(&pool)], 1);
+}
@@ -0,0 +1,87 @@
+#![cfg(test)]
+
+use alloy_primitives::{address, b256, Address, Bytes, FixedBytes, B256, I256, U256};
+use ethabi_static::{AbiType, AddressZcp, BytesZcp, DecodeStatic, FixedBytesZcp};
+use hex_literal::hex;
+
+#[test]
+fn decode_alloy_types() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Thingy {
+        a: Address,
+        b: Address,
+        c: U256,
+        d: Bytes,
+        e: Vec<Bytes>,
+        f: FixedBytes<8>,
+    }
+
+    let input = hex!("00000000000000000000000012345678912345678911111111111111111111110000000000000000000000001234567891234567891111111111111111111222000000000000000000000000000000000000000000000000000000000000303900000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001001122334455667788000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a10000000000000000000000000000000000000000000000000ff000000000000000000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000e00000000000000000000000000000000000000000000000000000000000000120000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000001a000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000002200000000000000000000000000000000000000000000000000000000000000260000000000000000000000000000000000000000000000000000000000000000213370000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002b33f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003a4b05000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001370000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000116000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001ff00000000000000000000000000000000000000000000000000000000000000");
+
+    let thingy = Thingy::decode(&input).unwrap();
+    assert_eq!(
+        thingy.a,
+        address!("1234567891234567891111111111111111111111")
+    );
+    assert_eq!(
+        thingy.b,
+        address!("1234567891234567891111111111111111111222")
+    );
+    assert_eq!(thingy.c, U256::from(12345_u32));
+    assert_eq!(
+        thingy.d,
+        Bytes::from_static(&hex!(
+            "10000000000000000000000000000000000000000000000000ff"
+        ))
+    );
+    assert_eq!(
+        thingy.e,
+        vec![
+            Bytes::from_static(&[0x13, 0x37]),
+            Bytes::from_static(&[0xb3, 0x3f]),
+            Bytes::from_static(&[0xa4, 0xb0, 0x50]),
+            Bytes::from_static(&[0x37]),
+            Bytes::from_static(&[0x0b]),
+            Bytes::from_static(&[0x16]),
+            Bytes::from_static(&[0xff]),
+        ]
+    );
+    assert_eq!(thingy.f, FixedBytes(hex!("1122334455667788")));
+    assert_eq!(
+        Thingy::abi_type(),
+        "(address,address,uint256,bytes,bytes[],bytes8)"
+    );
+}
+
+#[test]
+fn decode_alloy_signed_and_words() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Tick {
+        tick: I256,
+        hash: B256,
+    }
+
+    let input = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff6c5ef59b10f9bc8a9b6b1dc4cc6a6b3be6b7a1aa6f93c4ef3d3a3b2a4e5f6a7b8");
+    let tick = Tick::decode(&input).unwrap();
+    assert_eq!(tick.tick, I256::try_from(-10_i64).unwrap());
+    assert_eq!(
+        tick.hash,
+        b256!("c5ef59b10f9bc8a9b6b1dc4cc6a6b3be6b7a1aa6f93c4ef3d3a3b2a4e5f6a7b8")
+    );
+}
+
+#[test]
+fn zero_copy_conversions() {
+    let raw = hex!("1234567891234567891111111111111111111111");
+    let zcp = AddressZcp(&raw);
+    let owned = Address::from(AddressZcp(&raw));
+    assert_eq!(AddressZcp::from(&owned), zcp);
+
+    let raw = hex!("11223344");
+    let owned = FixedBytes::<4>::from(FixedBytesZcp(&raw));
+    assert_eq!(owned, FixedBytes(raw));
+    assert_eq!(FixedBytesZcp::from(&owned), FixedBytesZcp(&raw));
+
+    let owned = Bytes::from(BytesZcp(&raw));
+    assert_eq!(BytesZcp::from(&owned), BytesZcp(&raw));
+}
@@ -0,0 +1,369 @@
+#![cfg(test)]
+
+use ethabi::Token;
+use ethabi_static::{
+    AddressZcp, Array, BytesZcp, DecodeStatic, StrZcp, Tuple, Tuples, Wrapped, WrappedCall,
+    WrappedTuple,
+};
+use ethereum_types::{H160, U256};
+
+#[derive(Debug, PartialEq, DecodeStatic)]
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+#[derive(Debug, PartialEq, DecodeStatic)]
+struct Call<'a> {
+    target: AddressZcp<'a>,
+    data: BytesZcp<'a>,
+}
+
+fn call(target: u8, data: &[u8]) -> Token {
+    Token::Tuple(vec![
+        Token::Address(H160([target; 20])),
+        Token::Bytes(data.to_vec()),
+    ])
+}
+
+#[test]
+fn dynamic_values_decode_top_level() {
+    let input = ethabi::encode(&[Token::Bytes(vec![1, 2, 3])]);
+    assert_eq!(BytesZcp::decode(&input).unwrap(), BytesZcp(&[1, 2, 3]));
+    assert_eq!(
+        BytesZcp::decode_strict(&input).unwrap(),
+        BytesZcp(&[1, 2, 3])
+    );
+
+    let input = ethabi::encode(&[call(0x11, &[4])]);
+    let Tuple(decoded) = Tuple::<Call>::decode(&input).unwrap();
+    assert_eq!(decoded.target, AddressZcp(&[0x11; 20]));
+    assert_eq!(decoded.data, BytesZcp(&[4]));
+}
+
+#[test]
+fn nested_static_struct_head_size() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Segment<'a> {
+        from: Point,
+        to: Point,
+        label: BytesZcp<'a>,
+        points: [Point; 2],
+        end: U256,
+    }
+
+    let point = |x: u64, y: u64| Token::Tuple(vec![Token::Uint(x.into()), Token::Uint(y.into())]);
+    let input = ethabi::encode(&[
+        point(1, 2),
+        point(3, 4),
+        Token::Bytes(b"seg".to_vec()),
+        Token::FixedArray(vec![point(5, 6), point(7, 8)]),
+        Token::Uint(9.into()),
+    ]);
+    let expected = Segment {
+        from: Point { x: 1, y: 2 },
+        to: Point { x: 3, y: 4 },
+        label: BytesZcp(b"seg"),
+        points: [Point { x: 5, y: 6 }, Point { x: 7, y: 8 }],
+        end: U256::from(9),
+    };
+    assert_eq!(Segment::decode(&input).unwrap(), expected);
+    assert_eq!(Segment::decode_strict(&input).unwrap(), expected);
+}
+
+#[test]
+fn dynamic_values_compose_as_fields() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Batch<'a> {
+        nonce: u64,
+        calls: Tuples<Call<'a>>,
+        first: Tuple<Call<'a>>,
+        payload: Wrapped<Point>,
+        hooks: [BytesZcp<'a>; 2],
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Outer<'a> {
+        owner: AddressZcp<'a>,
+        batch: Tuple<Batch<'a>>,
+        batches: Tuples<Batch<'a>>,
+    }
+
+    let batch = |nonce: u64| {
+        Token::Tuple(vec![
+            Token::Uint(nonce.into()),
+            Token::Array(vec![call(0x22, &[1]), call(0x33, &[2, 2])]),
+            call(0x44, &[3, 3, 3]),
+            Token::Bytes(ethabi::encode(&[
+                Token::Uint(nonce.into()),
+                Token::Uint((nonce + 1).into()),
+            ])),
+            Token::FixedArray(vec![Token::Bytes(vec![5]), Token::Bytes(vec![6, 6])]),
+        ])
+    };
+    let input = ethabi::encode(&[
+        Token::Address(H160([0x55; 20])),
+        batch(1),
+        Token::Array(vec![batch(2), batch(3)]),
+    ]);
+
+    let outer = Outer::decode(&input).unwrap();
+    assert_eq!(outer, Outer::decode_strict(&input).unwrap());
+    assert_eq!(outer.owner, AddressZcp(&[0x55; 20]));
+    for (batch, nonce) in [&outer.batch.0, &outer.batches[0], &outer.batches[1]]
+        .into_iter()
+        .zip(1..)
+    {
+        assert_eq!(batch.nonce, nonce);
+        assert_eq!(batch.calls.len(), 2);
+        assert_eq!(batch.calls[1].target, AddressZcp(&[0x33; 20]));
+        assert_eq!(batch.calls[1].data, BytesZcp(&[2, 2]));
+        assert_eq!(batch.first.0.data, BytesZcp(&[3, 3, 3]));
+        assert_eq!(
+            batch.payload.0,
+            Point {
+                x: nonce,
+                y: nonce + 1
+            }
+        );
+        assert_eq!(batch.hooks, [BytesZcp(&[5]), BytesZcp(&[6, 6])]);
+    }
+}
+
+#[test]
+fn decode_from_head_with_base() {
+    // `(uint256, (address,bytes))` decoding the inner tuple's `bytes` by hand
+    let input = ethabi::encode(&[Token::Uint(7.into()), call(0x66, &[8, 9])]);
+    let inner = as_offset(&input[32..64]);
+    let data = BytesZcp::decode_from_head(&input, inner, inner + 32).unwrap();
+    assert_eq!(data, BytesZcp(&[8, 9]));
+    // the derived struct gives the same from its start
+    assert_eq!(Call::decode_static(&input, inner).unwrap().data, data);
+}
+
+fn as_offset(word: &[u8]) -> usize {
+    U256::from_big_endian(word).as_usize()
+}
+
+#[test]
+fn vec_of_dynamic_structs() {
+    // `execute(Order[] orders, string memo)`
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Order<'a> {
+        maker: AddressZcp<'a>,
+        amount: u64,
+        signature: BytesZcp<'a>,
+        tags: Vec<StrZcp<'a>>,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Execute<'a> {
+        orders: Vec<Order<'a>>,
+        memo: StrZcp<'a>,
+        routes: Vec<Vec<u32>>,
+    }
+
+    let order = |maker: u8, tags: &[&str]| {
+        Token::Tuple(vec![
+            Token::Address(H160([maker; 20])),
+            Token::Uint((maker as u64 * 100).into()),
+            Token::Bytes(vec![maker; 65]),
+            Token::Array(tags.iter().map(|t| Token::String(t.to_string())).collect()),
+        ])
+    };
+    let route =
+        |hops: &[u32]| Token::Array(hops.iter().map(|h| Token::Uint((*h).into())).collect());
+    let input = ethabi::encode(&[
+        Token::Array(vec![
+            order(1, &["fok"]),
+            order(2, &[]),
+            order(3, &["gtc", "ü"]),
+        ]),
+        Token::String("batch #7".into()),
+        Token::Array(vec![route(&[500, 3000]), route(&[]), route(&[100])]),
+    ]);
+
+    let execute = Execute::decode(&input).unwrap();
+    assert_eq!(execute, Execute::decode_strict(&input).unwrap());
+    assert_eq!(execute.orders.len(), 3);
+    for (order, maker) in execute.orders.iter().zip(1_u8..) {
+        assert_eq!(order.maker, AddressZcp(&[maker; 20]));
+        assert_eq!(order.amount, maker as u64 * 100);
+        assert_eq!(order.signature.0, &[maker; 65][..]);
+    }
+    assert_eq!(execute.orders[0].tags, vec![StrZcp("fok")]);
+    assert!(execute.orders[1].tags.is_empty());
+    assert_eq!(execute.orders[2].tags, vec![StrZcp("gtc"), StrZcp("ü")]);
+    assert_eq!(execute.memo, StrZcp("batch #7"));
+    assert_eq!(execute.routes, vec![vec![500, 3000], vec![], vec![100]]);
+
+    // the strings are not UTF-8
+    let mut invalid = input.clone();
+    let memo = invalid.windows(8).position(|w| w == b"batch #7").unwrap();
+    invalid[memo] = 0xff;
+    assert!(Execute::decode(&invalid).is_err());
+    assert!(Execute::decode_strict(&invalid).is_err());
+
+    // the second order's tail points at the first's
+    let mut repeated = input.clone();
+    let orders = 32 * 3 + 32;
+    repeated[orders + 32..orders + 64].copy_from_slice(&input[orders..orders + 32]);
+    assert!(Execute::decode(&repeated).is_ok());
+    assert!(Execute::decode_strict(&repeated).is_err());
+}
+
+#[test]
+fn field_layout_comes_from_the_type_not_its_name() {
+    mod user {
+        use ethabi_static::DecodeStatic;
+
+        /// Static despite sharing a name with alloy's `bytes`
+        #[derive(Debug, PartialEq)]
+        pub struct Bytes(pub u64);
+
+        impl<'a> DecodeStatic<'a> for Bytes {
+            fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+                u64::decode_static(buf, offset).map(Bytes)
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Pair {
+        a: user::Bytes,
+        b: u64,
+    }
+
+    let input = ethabi::encode(&[Token::Uint(1.into()), Token::Uint(2.into())]);
+    let expected = Pair {
+        a: user::Bytes(1),
+        b: 2,
+    };
+    assert_eq!(Pair::HEAD_SIZE, 64);
+    assert_eq!(Pair::decode(&input), Ok(expected));
+    assert_eq!(Pair::decode_strict(&input).unwrap().a, user::Bytes(1));
+}
+
+#[test]
+fn wrapped_payloads() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Transfer<'a> {
+        to: AddressZcp<'a>,
+        amount: U256,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Wrappers<'a> {
+        point: Wrapped<Point>,
+        call: WrappedTuple<Call<'a>>,
+        transfer: WrappedCall<Transfer<'a>>,
+        forwarded: WrappedCall<Call<'a>>,
+    }
+
+    let mut transfer = vec![0xa9, 0x05, 0x9c, 0xbb];
+    transfer.extend(ethabi::encode(&[
+        Token::Address(H160([0x77; 20])),
+        Token::Uint(1000.into()),
+    ]));
+    let mut forwarded = vec![1, 2, 3, 4];
+    forwarded.extend(ethabi::encode(&[
+        Token::Address(H160([0x88; 20])),
+        Token::Bytes(transfer.clone()),
+    ]));
+    let point = ethabi::encode(&[Token::Uint(1.into()), Token::Uint(2.into())]);
+    let tuple = ethabi::encode(&[call(0x99, &[5, 6])]);
+    let encode = |point: &[u8], tuple: &[u8], transfer: &[u8]| {
+        ethabi::encode(&[
+            Token::Bytes(point.to_vec()),
+            Token::Bytes(tuple.to_vec()),
+            Token::Bytes(transfer.to_vec()),
+            Token::Bytes(forwarded.clone()),
+        ])
+    };
+
+    let input = encode(&point, &tuple, &transfer);
+    let decoded = Wrappers::decode(&input).unwrap();
+    assert_eq!(decoded, Wrappers::decode_strict(&input).unwrap());
+    assert_eq!(decoded.point.0, Point { x: 1, y: 2 });
+    assert_eq!(decoded.call.0.target, AddressZcp(&[0x99; 20]));
+    assert_eq!(decoded.call.0.data, BytesZcp(&[5, 6]));
+    assert_eq!(decoded.transfer.selector, [0xa9, 0x05, 0x9c, 0xbb]);
+    assert_eq!(decoded.transfer.args.to, AddressZcp(&[0x77; 20]));
+    assert_eq!(decoded.transfer.args.amount, U256::from(1000));
+    assert_eq!(decoded.forwarded.selector, [1, 2, 3, 4]);
+    assert_eq!(decoded.forwarded.args.data, BytesZcp(&transfer));
+
+    // each payload cut short of its head
+    for input in [
+        encode(&point[..63], &tuple, &transfer),
+        encode(&point, &tuple[..31], &transfer),
+        encode(&point, &tuple[..64], &transfer),
+        encode(&point, &tuple, &transfer[..67]),
+        encode(&point, &tuple, &transfer[..3]),
+    ] {
+        assert!(Wrappers::decode(&input).is_err());
+        assert!(Wrappers::decode_strict(&input).is_err());
+    }
+}
+
+#[test]
+fn overstated_lengths_are_errors() {
+    // the length word claims 3 elements but only one follows
+    let mut payload = ethabi::encode(&[Token::Array(vec![Token::Uint(7.into())])]);
+    payload[63] = 3;
+    let input = ethabi::encode(&[Token::Bytes(payload.clone())]);
+    assert!(Wrapped::<Vec<U256>>::decode(&input).is_err());
+    assert!(Wrapped::<Vec<u64>>::decode(&input).is_err());
+    assert!(Wrapped::<Vec<AddressZcp>>::decode(&input).is_err());
+    assert!(Wrapped::<Vec<bool>>::decode(&input).is_err());
+    assert!(Vec::<U256>::decode(&payload).is_err());
+    assert!(Array::<U256>::decode(&payload).is_err());
+}
+
+#[test]
+fn decode_into_reuses_allocations() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Block<'a> {
+        number: u64,
+        routes: Vec<Vec<u32>>,
+        calls: Tuples<Call<'a>>,
+        hashes: Array<BytesZcp<'a>>,
+        pending: [Vec<u64>; 2],
+        #[ethabi(rust_only)]
+        seen: bool,
+    }
+
+    let uints = |xs: &[u64]| Token::Array(xs.iter().map(|x| Token::Uint((*x).into())).collect());
+    let block = |number: u64, routes: &[&[u64]], calls: u8| {
+        ethabi::encode(&[
+            Token::Uint(number.into()),
+            Token::Array(routes.iter().map(|r| uints(r)).collect()),
+            Token::Array((0..calls).map(|i| call(i, &[i; 3])).collect()),
+            Token::Array((0..calls).map(|i| Token::Bytes(vec![i; 32])).collect()),
+            Token::FixedArray(vec![uints(&[number; 4]), uints(&[])]),
+        ])
+    };
+    let first = block(1, &[&[500, 3000, 100], &[10_000]], 3);
+    let second = block(2, &[&[500], &[3000, 100]], 2);
+
+    let mut decoded = Block::decode(&first).unwrap();
+    decoded.seen = true;
+    let routes = decoded.routes.as_ptr();
+    let first_route = decoded.routes[0].as_ptr();
+    let calls = decoded.calls.as_ptr();
+    let pending = decoded.pending[0].as_ptr();
+
+    decoded.decode_into(&second).unwrap();
+    assert_eq!(decoded, Block::decode(&second).unwrap());
+    assert_eq!(decoded.routes, vec![vec![500], vec![3000, 100]]);
+    assert_eq!(decoded.calls.len(), 2);
+    assert_eq!(decoded.calls[1].data, BytesZcp(&[1, 1, 1]));
+    assert_eq!(decoded.pending[0], vec![2; 4]);
+    assert!(!decoded.seen);
+    // the same allocations hold the new values
+    assert_eq!(decoded.routes.as_ptr(), routes);
+    assert_eq!(decoded.routes[0].as_ptr(), first_route);
+    assert_eq!(decoded.calls.as_ptr(), calls);
+    assert_eq!(decoded.pending[0].as_ptr(), pending);
+
+    // growing past the old length
+    decoded.decode_into(&first).unwrap();
+    assert_eq!(decoded, Block::decode(&first).unwrap());
+}
@@ -3,7 +3,10 @@
 use crate::V2_RESULTS;
 
 use ethabi::{ParamType, Token};
-use ethabi_static::{AddressZcp, BytesZcp, DecodeStatic, FixedBytesZcp, Tuple, Tuples, Wrapped};
+use ethabi_static::{
+    AbiType, AddressZcp, BytesZcp, DecodeStatic, FixedBytesZcp, IntoOwned, StrZcp, Tuple, Tuples,
+    Wrapped,
+};
 use ethereum_types::U256;
 use hex_literal::hex;
 
@@ -297,6 +300,7 @@ fn statics_list_fixed_bytes() {
 }
 
 #[test]
+#[allow(clippy::single_match)]
 fn eth_abi_results2() {
     let params = [ParamType::Array(Box::new(ParamType::Tuple(vec![
         ParamType::Bool,
@@ -346,3 +350,525 @@ fn as_tuple() {
     assert_eq!(res.bridge_order_offset, U256::from(320));
     println!("{:?}", res);
 }
+
+#[test]
+fn field_attributes() {
+    fn decode_doubled(buf: &[u8], offset: usize) -> Result<u64, ()> {
+        u64::decode_static(buf, offset).map(|x| x * 2)
+    }
+
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Attrs<'a> {
+        #[ethabi(with = "decode_doubled", abi_type = "uint64")]
+        a: u64,
+        #[ethabi(skip, default = "7", abi_type = "uint32")]
+        b: u32,
+        #[ethabi(abi_type = "uint112")]
+        c: u128,
+        d: AddressZcp<'a>,
+    }
+
+    let input = hex!("0000000000000000000000000000000000000000000000000000000000000015000000000000000000000000000000000000000000000000000000000000ffff000000000000000000000000000000000000000000000000000000000000002a0000000000000000000000001234567891234567891111111111111111111111");
+    assert_eq!(
+        Attrs::decode(&input).unwrap(),
+        Attrs {
+            a: 42,
+            b: 7,
+            c: 42,
+            d: AddressZcp(&hex!("1234567891234567891111111111111111111111")),
+        }
+    );
+    assert_eq!(Attrs::abi_type(), "(uint64,uint32,uint112,address)");
+}
+
+#[test]
+fn skipped_field_without_abi_type() {
+    #[derive(Debug, PartialEq)]
+    struct Custom(u64);
+
+    // no `AbiType` so neither is the struct's
+    impl<'a> DecodeStatic<'a> for Custom {
+        fn decode_static(buf: &'a [u8], offset: usize) -> Result<Self, ()> {
+            u64::decode_static(buf, offset).map(Custom)
+        }
+    }
+
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Skipped {
+        a: Custom,
+        #[ethabi(skip)]
+        b: String,
+    }
+
+    let input = hex!("0000000000000000000000000000000000000000000000000000000000000015000000000000000000000000000000000000000000000000000000000000ffff");
+    assert_eq!(
+        Skipped::decode(&input).unwrap(),
+        Skipped {
+            a: Custom(21),
+            b: String::new(),
+        }
+    );
+}
+
+#[test]
+fn abi_type_signatures() {
+    #[allow(dead_code)]
+    #[derive(Debug, DecodeStatic)]
+    struct Order<'a> {
+        maker: AddressZcp<'a>,
+        amounts: Vec<U256>,
+        data: BytesZcp<'a>,
+        salt: FixedBytesZcp<'a, 32>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, DecodeStatic)]
+    struct Fill<'a> {
+        orders: Tuples<Order<'a>>,
+        flags: [bool; 2],
+        payload: Wrapped<Order<'a>>,
+    }
+
+    assert_eq!(
+        Order::signature("fill"),
+        "fill(address,uint256[],bytes,bytes32)"
+    );
+    assert_eq!(
+        Fill::abi_type(),
+        "((address,uint256[],bytes,bytes32)[],bool[2],bytes)"
+    );
+}
+
+#[test]
+fn ignored_and_rust_only_fields() {
+    let input = hex!("0000000000000000000000000000000000000000000000000000000000000037000000000000000000000000000000000000000000000000000000000000022b00000000000000000000000000000000000000000000000000000000000015b3");
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Numero {
+        #[ethabi(rust_only, default = "String::from(\"numero\")")]
+        label: String,
+        a: u8,
+        #[ethabi(ignore, abi_type = "uint16")]
+        b: u16,
+        #[ethabi(rust_only)]
+        seen: bool,
+        c: u32,
+    }
+
+    assert_eq!(
+        Numero::decode(&input).unwrap(),
+        Numero {
+            label: "numero".into(),
+            a: 55,
+            b: 0,
+            seen: false,
+            c: 5_555,
+        }
+    );
+    assert_eq!(Numero::abi_type(), "(uint8,uint16,uint32)");
+}
+
+#[test]
+fn into_owned() {
+    #[derive(Debug, PartialEq, DecodeStatic, IntoOwned)]
+    struct Thingy<'a> {
+        a: AddressZcp<'a>,
+        c: U256,
+        d: BytesZcp<'a>,
+        e: Vec<BytesZcp<'a>>,
+        f: FixedBytesZcp<'a, 8>,
+        #[ethabi(rust_only)]
+        g: u32,
+    }
+
+    #[derive(Debug, PartialEq, DecodeStatic, IntoOwned)]
+    struct UniswapV2Reserves {
+        r0: u128,
+        r1: u128,
+    }
+
+    #[derive(Debug, PartialEq, DecodeStatic, IntoOwned)]
+    struct GenericResult3<T> {
+        #[ethabi(skip)]
+        ok: bool,
+        data: Wrapped<T>,
+    }
+
+    let input = ethabi::encode(&[
+        Token::Address(hex!("1234567891234567891111111111111111111111").into()),
+        Token::Uint(U256::from(12345_u32)),
+        Token::Bytes(vec![0x13, 0x37]),
+        Token::Array(vec![
+            Token::Bytes(vec![0xb3, 0x3f]),
+            Token::Bytes(vec![0xff]),
+        ]),
+        Token::FixedBytes(hex!("1122334455667788").to_vec()),
+    ]);
+    let owned: ThingyOwned = {
+        let thingy = Thingy::decode(&input).unwrap();
+        thingy.into_owned()
+    };
+    assert_eq!(
+        owned,
+        ThingyOwned {
+            a: hex!("1234567891234567891111111111111111111111").into(),
+            c: U256::from(12345_u32),
+            d: vec![0x13, 0x37],
+            e: vec![vec![0xb3, 0x3f], vec![0xff]],
+            f: hex!("1122334455667788"),
+            g: 0,
+        }
+    );
+
+    let out: Tuples<GenericResult3<UniswapV2Reserves>> =
+        DecodeStatic::decode(V2_RESULTS).expect("it decodes");
+    let owned: Tuples<GenericResult3Owned<UniswapV2Reserves>> = out.into_owned();
+    assert_eq!(
+        owned.0[0],
+        GenericResult3Owned {
+            ok: false,
+            data: Wrapped(UniswapV2ReservesOwned {
+                r0: 4046096857213803749746,
+                r1: 7521704656452
+            })
+        }
+    );
+}
+
+#[test]
+fn solidity_enums() {
+    #[derive(Debug, Clone, Copy, PartialEq, DecodeStatic, IntoOwned)]
+    #[repr(u8)]
+    enum Operation {
+        Call,
+        DelegateCall,
+    }
+    #[derive(Debug, Clone, Copy, PartialEq, DecodeStatic, IntoOwned)]
+    enum OrderSide {
+        Buy = 1,
+        Sell = 2,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic, IntoOwned)]
+    struct SafeTx<'a> {
+        to: AddressZcp<'a>,
+        data: BytesZcp<'a>,
+        operation: Operation,
+        sides: [OrderSide; 2],
+    }
+
+    let encode = |operation: u8, side: u8| {
+        ethabi::encode(&[
+            Token::Address([0x11; 20].into()),
+            Token::Bytes(vec![1, 2]),
+            Token::Uint(operation.into()),
+            Token::FixedArray(vec![Token::Uint(2.into()), Token::Uint(side.into())]),
+        ])
+    };
+    let input = encode(1, 1);
+    let tx = SafeTx::decode(&input).unwrap();
+    assert_eq!(tx, SafeTx::decode_strict(&input).unwrap());
+    assert_eq!(tx.operation, Operation::DelegateCall);
+    assert_eq!(tx.sides, [OrderSide::Sell, OrderSide::Buy]);
+    assert_eq!(tx.into_owned().operation, Operation::DelegateCall);
+    assert_eq!(SafeTx::abi_type(), "(address,bytes,uint8,uint8[2])");
+
+    // no variant for the discriminant
+    for input in [encode(2, 1), encode(0, 0), encode(0, 3)] {
+        assert!(SafeTx::decode(&input).is_err());
+        assert!(SafeTx::decode_strict(&input).is_err());
+    }
+    // a value above 255 e.g. 256 isn't `Call`
+    let mut wide = encode(0, 1);
+    wide[32 * 2 + 30] = 1;
+    assert!(SafeTx::decode(&wide).is_err());
+    assert!(SafeTx::decode_strict(&wide).is_err());
+}
+
+#[test]
+fn transparent_newtypes() {
+    // `type Fee is uint24`
+    #[derive(Debug, Clone, Copy, PartialEq, DecodeStatic)]
+    #[ethabi(transparent)]
+    struct Fee(#[ethabi(abi_type = "uint24")] u32);
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    #[ethabi(transparent)]
+    struct PoolId<'a>(FixedBytesZcp<'a, 32>);
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    #[ethabi(transparent)]
+    struct Memo<'a> {
+        text: StrZcp<'a>,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Pool<'a> {
+        id: PoolId<'a>,
+        memo: Memo<'a>,
+        fees: [Fee; 2],
+        tiers: Vec<Fee>,
+    }
+
+    assert_eq!(Pool::abi_type(), "(bytes32,string,uint24[2],uint24[])");
+    assert_eq!((Memo::DYNAMIC, Memo::HEAD_SIZE), (true, 32));
+    assert_eq!(<[Fee; 2]>::HEAD_SIZE, 64);
+
+    let input = ethabi::encode(&[
+        Token::FixedBytes(vec![0xab; 32]),
+        Token::String("0.3%".into()),
+        Token::FixedArray(vec![Token::Uint(500.into()), Token::Uint(3000.into())]),
+        Token::Array(vec![Token::Uint(100.into())]),
+    ]);
+    let pool = Pool::decode(&input).unwrap();
+    assert_eq!(pool, Pool::decode_strict(&input).unwrap());
+    assert_eq!(pool.id, PoolId(FixedBytesZcp(&[0xab; 32])));
+    assert_eq!(pool.memo.text, StrZcp("0.3%"));
+    assert_eq!(pool.fees, [Fee(500), Fee(3000)]);
+    assert_eq!(pool.tiers, vec![Fee(100)]);
+
+    // decodes exactly like the inner type at the top level
+    let input = ethabi::encode(&[Token::String("memo".into())]);
+    assert_eq!(
+        Memo::decode(&input).unwrap().text,
+        StrZcp::decode(&input).unwrap()
+    );
+    assert_eq!(Memo::decode_strict(&input).unwrap().text, StrZcp("memo"));
+}
+
+#[test]
+fn flattened_sub_structs() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Price {
+        #[ethabi(abi_type = "uint160")]
+        sqrt_price_x96: U256,
+        #[ethabi(abi_type = "int24")]
+        tick: u32,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Observations {
+        index: u16,
+        cardinality: u16,
+        cardinality_next: u16,
+    }
+    // `slot0()` returns seven flat values
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Slot0 {
+        #[ethabi(flatten)]
+        price: Price,
+        #[ethabi(flatten)]
+        observations: Observations,
+        fee_protocol: u8,
+        unlocked: bool,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Snapshot<'a> {
+        #[ethabi(flatten)]
+        slot0: Slot0,
+        pool: Observations,
+        label: BytesZcp<'a>,
+    }
+
+    assert_eq!(
+        Slot0::abi_type(),
+        "(uint160,int24,uint16,uint16,uint16,uint8,bool)"
+    );
+    assert_eq!(Slot0::HEAD_SIZE, 7 * 32);
+    assert_eq!(
+        Snapshot::abi_type(),
+        "(uint160,int24,uint16,uint16,uint16,uint8,bool,(uint16,uint16,uint16),bytes)"
+    );
+
+    let slot0 = [
+        Token::Uint(U256::one() << 96),
+        Token::Uint(200.into()),
+        Token::Uint(3.into()),
+        Token::Uint(50.into()),
+        Token::Uint(100.into()),
+        Token::Uint(0.into()),
+        Token::Bool(true),
+    ];
+    let expected = Slot0 {
+        price: Price {
+            sqrt_price_x96: U256::one() << 96,
+            tick: 200,
+        },
+        observations: Observations {
+            index: 3,
+            cardinality: 50,
+            cardinality_next: 100,
+        },
+        fee_protocol: 0,
+        unlocked: true,
+    };
+    let input = ethabi::encode(&slot0);
+    assert_eq!(Slot0::decode(&input).unwrap(), expected);
+    assert_eq!(Slot0::decode_strict(&input).unwrap(), expected);
+
+    // offsets in the parent count the flattened head words
+    let mut snapshot = slot0.to_vec();
+    snapshot.push(Token::Tuple(vec![
+        Token::Uint(1.into()),
+        Token::Uint(2.into()),
+        Token::Uint(3.into()),
+    ]));
+    snapshot.push(Token::Bytes(vec![7; 40]));
+    let input = ethabi::encode(&snapshot);
+    let decoded = Snapshot::decode(&input).unwrap();
+    assert_eq!(decoded, Snapshot::decode_strict(&input).unwrap());
+    assert_eq!(decoded.slot0, expected);
+    assert_eq!(decoded.pool.cardinality_next, 3);
+    assert_eq!(decoded.label, BytesZcp(&[7; 40]));
+}
+
+#[test]
+fn optional_trailing_fields() {
+    // older pools return fewer values from `slot0()`
+    #[derive(Debug, PartialEq, DecodeStatic, IntoOwned)]
+    struct Slot0 {
+        sqrt_price_x96: U256,
+        tick: u32,
+        #[ethabi(optional)]
+        fee_protocol: Option<u8>,
+        #[ethabi(optional)]
+        unlocked: Option<bool>,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct PoolInfo<'a> {
+        fee: u32,
+        name: StrZcp<'a>,
+        #[ethabi(optional)]
+        hooks: Option<BytesZcp<'a>>,
+        #[ethabi(optional)]
+        tick_spacing: Option<u32>,
+    }
+
+    assert_eq!(Slot0::abi_type(), "(uint256,uint32,uint8,bool)");
+    assert_eq!(PoolInfo::abi_type(), "(uint32,string,bytes,uint32)");
+
+    let words = [
+        Token::Uint(7.into()),
+        Token::Uint(200.into()),
+        Token::Uint(4.into()),
+        Token::Bool(true),
+    ];
+    for (len, fee_protocol, unlocked) in [
+        (2, None, None),
+        (3, Some(4), None),
+        (4, Some(4), Some(true)),
+    ] {
+        let input = ethabi::encode(&words[..len]);
+        let slot0 = Slot0::decode(&input).unwrap();
+        assert_eq!(slot0, Slot0::decode_strict(&input).unwrap());
+        assert_eq!(slot0.tick, 200);
+        assert_eq!(slot0.fee_protocol, fee_protocol);
+        assert_eq!(slot0.into_owned().unlocked, unlocked);
+    }
+    // required fields are still required
+    assert!(Slot0::decode_strict(&ethabi::encode(&words[..1])).is_err());
+
+    // the head ends where the name's tail starts
+    let values = [
+        Token::Uint(3000.into()),
+        Token::String("WETH/USDC".into()),
+        Token::Bytes(vec![0xaa; 20]),
+        Token::Uint(60.into()),
+    ];
+    for (len, hooks, tick_spacing) in [
+        (2, None, None),
+        (3, Some(BytesZcp(&[0xaa; 20])), None),
+        (4, Some(BytesZcp(&[0xaa; 20])), Some(60)),
+    ] {
+        let input = ethabi::encode(&values[..len]);
+        let info = PoolInfo::decode(&input).unwrap();
+        assert_eq!(info, PoolInfo::decode_strict(&input).unwrap());
+        assert_eq!(info.name, StrZcp("WETH/USDC"));
+        assert_eq!((info.hooks, info.tick_spacing), (hooks, tick_spacing));
+    }
+}
+
+#[test]
+fn exact_size_static_structs() {
+    // `getReserves()`
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct UniswapV2Reserves {
+        reserve0: u128,
+        reserve1: u128,
+        block_timestamp_last: u32,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Pair<'a> {
+        token0: AddressZcp<'a>,
+        token1: AddressZcp<'a>,
+        reserves: UniswapV2Reserves,
+        fees: [u16; 2],
+    }
+
+    assert_eq!(UniswapV2Reserves::ENCODED_SIZE, 96);
+    assert_eq!(Pair::ENCODED_SIZE, 7 * 32);
+
+    let input = ethabi::encode(&[
+        Token::Uint(1000.into()),
+        Token::Uint(2000.into()),
+        Token::Uint(1_700_000_000.into()),
+    ]);
+    let exact: &[u8; UniswapV2Reserves::ENCODED_SIZE] = input.as_slice().try_into().unwrap();
+    let reserves = UniswapV2Reserves::decode_exact(exact).unwrap();
+    assert_eq!(
+        reserves,
+        UniswapV2Reserves::decode_exact_slice(&input).unwrap()
+    );
+    assert_eq!(reserves.block_timestamp_last, 1_700_000_000);
+
+    // e.g. `token0()` return data or an extra word
+    assert!(UniswapV2Reserves::decode_exact_slice(&input[..32]).is_err());
+    let mut trailing = input.clone();
+    trailing.extend_from_slice(&[0; 32]);
+    assert!(UniswapV2Reserves::decode(&trailing).is_ok());
+    assert!(UniswapV2Reserves::decode_exact_slice(&trailing).is_err());
+
+    let input = ethabi::encode(&[
+        Token::Address([0x11; 20].into()),
+        Token::Address([0x22; 20].into()),
+        Token::Uint(1.into()),
+        Token::Uint(2.into()),
+        Token::Uint(3.into()),
+        Token::FixedArray(vec![Token::Uint(30.into()), Token::Uint(5.into())]),
+    ]);
+    let pair = Pair::decode_exact_slice(&input).unwrap();
+    assert_eq!(pair, Pair::decode(&input).unwrap());
+    assert_eq!(pair.token1, AddressZcp(&[0x22; 20]));
+    assert_eq!(pair.fees, [30, 5]);
+}
+
+#[test]
+fn fields_named_like_decode_params() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Pos<'a> {
+        offset: u64,
+        buf: BytesZcp<'a>,
+        size: u64,
+        tag: u64,
+    }
+
+    let input = ethabi::encode(&[
+        Token::Uint(7.into()),
+        Token::Bytes(vec![1, 2, 3]),
+        Token::Uint(8.into()),
+        Token::Uint(9.into()),
+    ]);
+    let pos = Pos::decode(&input).unwrap();
+    assert_eq!(
+        pos,
+        Pos {
+            offset: 7,
+            buf: BytesZcp(&[1, 2, 3]),
+            size: 8,
+            tag: 9,
+        }
+    );
+    assert_eq!(pos, Pos::decode_strict(&input).unwrap());
+    let mut into = Pos {
+        offset: 0,
+        buf: BytesZcp(&[]),
+        size: 0,
+        tag: 0,
+    };
+    into.decode_into(&input).unwrap();
+    assert_eq!(into, pos);
+}
@@ -0,0 +1,120 @@
+#![cfg(test)]
+
+use ethabi_static::{AddressZcp, DecodeStatic, Eip712, Eip712Domain};
+use ethereum_types::{H160, U256};
+use hex_literal::hex;
+
+// the `Mail` example from the EIP-712 specification
+#[derive(Eip712)]
+struct Person<'a> {
+    name: &'a str,
+    wallet: H160,
+}
+
+#[derive(Eip712)]
+struct Mail<'a> {
+    from: Person<'a>,
+    to: Person<'a>,
+    contents: &'a str,
+}
+
+#[derive(Debug, DecodeStatic, Eip712)]
+struct Permit<'a> {
+    owner: AddressZcp<'a>,
+    spender: AddressZcp<'a>,
+    value: U256,
+    nonce: U256,
+    deadline: U256,
+}
+
+#[derive(Eip712)]
+#[ethabi(rename = "Order", rename_all = "camelCase")]
+struct OrderZcp<'a> {
+    sell_token: AddressZcp<'a>,
+    #[ethabi(abi_type = "uint32")]
+    valid_to: u64,
+    #[ethabi(rename = "appData")]
+    app_data_hash: [u8; 2],
+    #[ethabi(rust_only)]
+    _cached: bool,
+}
+
+fn ether_mail_domain() -> Eip712Domain<'static> {
+    Eip712Domain {
+        name: Some("Ether Mail"),
+        version: Some("1"),
+        chain_id: Some(U256::from(1)),
+        verifying_contract: Some(H160(hex!("cccccccccccccccccccccccccccccccccccccccc"))),
+        salt: None,
+    }
+}
+
+#[test]
+fn eip712_spec_mail() {
+    let mail = Mail {
+        from: Person {
+            name: "Cow",
+            wallet: H160(hex!("cd2a3d9f938e13cd947ec05abc7fe734df8dd826")),
+        },
+        to: Person {
+            name: "Bob",
+            wallet: H160(hex!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb")),
+        },
+        contents: "Hello, Bob!",
+    };
+
+    assert_eq!(
+        Mail::encode_type(),
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    assert_eq!(
+        Mail::type_hash(),
+        hex!("a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2")
+    );
+    assert_eq!(
+        mail.hash_struct(),
+        hex!("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+    );
+    assert_eq!(
+        ether_mail_domain().separator(),
+        hex!("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+    );
+    assert_eq!(
+        mail.signing_hash(&ether_mail_domain()),
+        hex!("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+    );
+}
+
+#[test]
+fn decoded_permit_hash() {
+    // an ABI encoded EIP-2612 `Permit` message
+    let calldata = hex!(
+        "0000000000000000000000001111111111111111111111111111111111111111"
+        "0000000000000000000000002222222222222222222222222222222222222222"
+        "00000000000000000000000000000000000000000000000000000000000f4240"
+        "0000000000000000000000000000000000000000000000000000000000000007"
+        "00000000000000000000000000000000000000000000000000000000ffffffff"
+    );
+    let permit = Permit::decode(&calldata).expect("it decodes");
+    assert_eq!(
+        Permit::encode_type(),
+        "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+    );
+    assert_eq!(
+        Permit::type_hash(),
+        hex!("6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9")
+    );
+
+    // hashStruct is the hash of the type hash followed by the words as encoded
+    let mut data = Permit::type_hash().to_vec();
+    data.extend_from_slice(&calldata);
+    assert_eq!(permit.hash_struct(), ethabi_static::keccak256(&data));
+}
+
+#[test]
+fn eip712_renamed_members() {
+    assert_eq!(
+        OrderZcp::encode_type(),
+        "Order(address sellToken,uint32 validTo,uint8[2] appData)"
+    );
+}
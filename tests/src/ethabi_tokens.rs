@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+use crate::V2_RESULTS;
+
+use ethabi::{ParamType, Token};
+use ethabi_static::{
+    AddressZcp, BytesZcp, DecodeStatic, FixedBytesZcp, FromToken, FromTokens, IntoToken,
+    IntoTokens, Tuples, Wrapped,
+};
+use ethereum_types::U256;
+use hex_literal::hex;
+
+#[test]
+fn derived_struct_tokens_round_trip() {
+    #[derive(Debug, PartialEq, DecodeStatic, IntoTokens, FromTokens)]
+    struct Thingy<'a> {
+        a: AddressZcp<'a>,
+        b: AddressZcp<'a>,
+        c: U256,
+        d: BytesZcp<'a>,
+        e: Vec<BytesZcp<'a>>,
+        f: FixedBytesZcp<'a, 8>,
+        #[ethabi(rust_only)]
+        g: bool,
+    }
+
+    let input = hex!("00000000000000000000000012345678912345678911111111111111111111110000000000000000000000001234567891234567891111111111111111111222000000000000000000000000000000000000000000000000000000000000303900000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001001122334455667788000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a10000000000000000000000000000000000000000000000000ff000000000000000000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000e00000000000000000000000000000000000000000000000000000000000000120000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000001a000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000002200000000000000000000000000000000000000000000000000000000000000260000000000000000000000000000000000000000000000000000000000000000213370000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002b33f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003a4b05000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001370000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000116000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001ff00000000000000000000000000000000000000000000000000000000000000");
+    let types = &[
+        ParamType::Address,
+        ParamType::Address,
+        ParamType::Uint(256_usize),
+        ParamType::Bytes,
+        ParamType::Array(Box::new(ParamType::Bytes)),
+        ParamType::FixedBytes(8),
+    ];
+    let tokens = ethabi::decode(types, &input).unwrap();
+
+    let thingy = Thingy::from_tokens(&tokens).unwrap();
+    assert_eq!(thingy, Thingy::decode(&input).unwrap());
+    assert_eq!(thingy.into_tokens(), tokens);
+    assert!(Thingy::from_tokens(&tokens[1..]).is_err());
+}
+
+#[test]
+fn tuples_into_token() {
+    #[derive(Debug, PartialEq, DecodeStatic, IntoTokens, FromTokens)]
+    struct Result3<'a> {
+        success: bool,
+        return_data: BytesZcp<'a>,
+    }
+
+    let params = [ParamType::Array(Box::new(ParamType::Tuple(vec![
+        ParamType::Bool,
+        ParamType::Bytes,
+    ])))];
+    let tokens = ethabi::decode(&params, V2_RESULTS).unwrap();
+
+    let out: Tuples<Result3<'_>> = DecodeStatic::decode(V2_RESULTS).unwrap();
+    assert_eq!(Tuples::<Result3<'_>>::from_token(&tokens[0]).unwrap(), out);
+    assert_eq!(Token::from(out), tokens[0]);
+}
+
+#[test]
+fn wrapped_token() {
+    #[derive(Debug, PartialEq, DecodeStatic, IntoTokens)]
+    struct UniswapV2Reserves {
+        r0: u128,
+        r1: u128,
+    }
+
+    let reserves = UniswapV2Reserves {
+        r0: 4046096857213803749746,
+        r1: 7521704656452,
+    };
+    let token = Token::from(Wrapped(reserves));
+    let Token::Bytes(ref encoded) = token else {
+        panic!("expected bytes");
+    };
+    assert_eq!(
+        encoded.as_slice(),
+        hex!("0000000000000000000000000000000000000000000000db56dfa67efd2ce172000000000000000000000000000000000000000000000000000006d74888be44")
+    );
+    assert_eq!(
+        Wrapped::<UniswapV2Reserves>::from_token(&token).unwrap(),
+        Wrapped(UniswapV2Reserves {
+            r0: 4046096857213803749746,
+            r1: 7521704656452,
+        })
+    );
+    assert_eq!(
+        Token::from(AddressZcp(&hex!(
+            "1234567891234567891111111111111111111111"
+        ))),
+        Token::Address(hex!("1234567891234567891111111111111111111111").into())
+    );
+    assert_eq!(
+        FixedBytesZcp::<4>(&hex!("11223344")).into_token(),
+        Token::FixedBytes(hex!("11223344").to_vec())
+    );
+}
@@ -3,8 +3,21 @@
 #[cfg(feature = "bench")]
 mod bench;
 
+mod address;
+mod alloy_interop;
+mod composition;
 mod derive_integration;
+mod eip712;
+mod ethabi_tokens;
+mod multicall;
+mod packed;
+mod serde_json;
+mod small_arrays;
+mod strict;
+mod ui;
+mod zero_copy;
 
+#[cfg(test)]
 const V2_RESULTS: &[u8] = &[
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -0,0 +1,250 @@
+#![cfg(test)]
+
+use crate::V2_RESULTS;
+
+use ethabi::{ParamType, Token};
+use ethabi_static::{
+    Aggregate3, Aggregate3Batch, Aggregate3Results, CallError, CallResult, DecodeStatic, Wrapped,
+    AGGREGATE3_SELECTOR,
+};
+use ethereum_types::{H160, U256};
+use hex_literal::hex;
+
+const GET_RESERVES: [u8; 4] = hex!("0902f1ac");
+
+#[derive(Debug, PartialEq, DecodeStatic)]
+struct UniswapV2Reserves {
+    r0: u128,
+    r1: u128,
+    timestamp: u32,
+}
+
+#[test]
+fn aggregate3_calldata_matches_ethabi() {
+    let pair = H160(hex!("b4e16d0168e52d35cacd2c6185b44281ec28c9dc"));
+    let token = H160(hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"));
+    let balance_of = [
+        hex!("70a08231").as_slice(),
+        &ethabi::encode(&[Token::Address(pair)]),
+    ]
+    .concat();
+
+    let calldata = Aggregate3::new()
+        .add_call(pair, true, GET_RESERVES.to_vec())
+        .add_call(token, false, balance_of.clone())
+        .add_call(pair, true, vec![])
+        .encode();
+
+    let call3 = ParamType::Tuple(vec![ParamType::Address, ParamType::Bool, ParamType::Bytes]);
+    let selector = ethabi::short_signature("aggregate3", &[ParamType::Array(Box::new(call3))]);
+    assert_eq!(selector, AGGREGATE3_SELECTOR);
+
+    let call = |target: H160, allow_failure: bool, data: Vec<u8>| {
+        Token::Tuple(vec![
+            Token::Address(target),
+            Token::Bool(allow_failure),
+            Token::Bytes(data),
+        ])
+    };
+    let expected = [
+        selector.as_slice(),
+        &ethabi::encode(&[Token::Array(vec![
+            call(pair, true, GET_RESERVES.to_vec()),
+            call(token, false, balance_of),
+            call(pair, true, vec![]),
+        ])]),
+    ]
+    .concat();
+    assert_eq!(calldata, expected);
+}
+
+#[test]
+fn aggregate3_results_decode() {
+    let out: Aggregate3Results<UniswapV2Reserves> =
+        DecodeStatic::decode(V2_RESULTS).expect("it decodes");
+    assert_eq!(out.len(), 4);
+    assert_eq!(
+        out[0],
+        Ok(Wrapped(UniswapV2Reserves {
+            r0: 4046096857213803749746,
+            r1: 7521704656452,
+            timestamp: 1683548951,
+        }))
+    );
+}
+
+#[test]
+fn aggregate3_failures_are_errors() {
+    let reserves = ethabi::encode(&[
+        Token::Uint(U256::from(1)),
+        Token::Uint(U256::from(2)),
+        Token::Uint(U256::from(3)),
+    ]);
+    let revert = hex!("08c379a0").to_vec();
+    let input = ethabi::encode(&[Token::Array(vec![
+        Token::Tuple(vec![Token::Bool(false), Token::Bytes(revert.clone())]),
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(reserves)]),
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![])]),
+    ])]);
+
+    let out: Aggregate3Results<UniswapV2Reserves> =
+        DecodeStatic::decode(&input).expect("it decodes");
+    let mut results = out.into_iter();
+    match results.next() {
+        Some(Err(CallError::Reverted(data))) => assert_eq!(data.0, revert.as_slice()),
+        other => panic!("expected a revert, got {:?}", other),
+    }
+    assert_eq!(
+        results.next(),
+        Some(Ok(Wrapped(UniswapV2Reserves {
+            r0: 1,
+            r1: 2,
+            timestamp: 3
+        })))
+    );
+    assert_eq!(results.next(), Some(Err(CallError::NoData)));
+    assert_eq!(results.next(), None);
+}
+
+#[test]
+fn short_return_data_is_an_error() {
+    let input = ethabi::encode(&[Token::Array(vec![Token::Tuple(vec![
+        Token::Bool(true),
+        Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+    ])])]);
+
+    assert!(Aggregate3Results::<U256>::decode(&input).is_err());
+    assert!(<(CallResult<U256>,)>::decode_aggregate3(&input).is_err());
+}
+
+#[test]
+fn overstated_return_data_length_is_an_error() {
+    // the return data is a `uint256[]` claiming 3 elements but holding one
+    let mut data = ethabi::encode(&[Token::Array(vec![Token::Uint(7.into())])]);
+    data[63] = 3;
+    let input = ethabi::encode(&[Token::Array(vec![Token::Tuple(vec![
+        Token::Bool(true),
+        Token::Bytes(data),
+    ])])]);
+
+    assert!(Aggregate3Results::<Vec<U256>>::decode(&input).is_err());
+    assert!(Aggregate3Results::<Vec<U256>>::decode_strict(&input).is_err());
+    assert!(<(CallResult<Vec<U256>>,)>::decode_aggregate3(&input).is_err());
+}
+
+#[test]
+fn aggregate3_results_decode_strict() {
+    let reserves = ethabi::encode(&[
+        Token::Uint(1.into()),
+        Token::Uint(0.into()),
+        Token::Uint(3.into()),
+    ]);
+    let encode = |success: Token, tail: Token| {
+        ethabi::encode(&[
+            Token::Array(vec![
+                Token::Tuple(vec![success, Token::Bytes(reserves.clone())]),
+                Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![1, 2])]),
+            ]),
+            tail,
+        ])
+    };
+    let input = encode(Token::Bool(true), Token::Uint(9.into()));
+    let strict = Aggregate3Results::<UniswapV2Reserves>::decode_strict(&input).unwrap();
+    assert_eq!(
+        strict,
+        Aggregate3Results::<UniswapV2Reserves>::decode(&input).unwrap()
+    );
+
+    // the array's tail ends before the next value's tail may start
+    #[derive(Debug, DecodeStatic)]
+    struct Outer<'a> {
+        results: Aggregate3Results<'a, UniswapV2Reserves>,
+        last: ethabi_static::BytesZcp<'a>,
+    }
+    let outer = ethabi::encode(&[
+        Token::Array(vec![Token::Tuple(vec![
+            Token::Bool(true),
+            Token::Bytes(reserves.clone()),
+        ])]),
+        Token::Bytes(vec![5]),
+    ]);
+    assert!(Outer::decode_strict(&outer).is_ok());
+    let mut aliased = outer.clone();
+    // point `last` at the zero `r1` word of the return data, an empty `bytes`
+    aliased[62] = 0x01;
+    aliased[63] = 0x00;
+    assert!(Outer::decode(&aliased).is_ok());
+    assert!(Outer::decode_strict(&aliased).is_err());
+
+    // a success flag other than 0/1
+    let mut dirty = input.clone();
+    let tuple = 64 + 32 + as_offset(&input[96..128]);
+    dirty[tuple + 31] = 2;
+    assert!(Aggregate3Results::<UniswapV2Reserves>::decode(&dirty).is_ok());
+    assert!(Aggregate3Results::<UniswapV2Reserves>::decode_strict(&dirty).is_err());
+}
+
+fn as_offset(word: &[u8]) -> usize {
+    usize::from_be_bytes(word[24..].try_into().unwrap())
+}
+
+#[test]
+fn aggregate3_batch_of_mixed_types() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Slot0 {
+        sqrt_price_x96: U256,
+        tick: u32,
+    }
+
+    let input = ethabi::encode(&[Token::Array(vec![
+        Token::Tuple(vec![
+            Token::Bool(true),
+            Token::Bytes(ethabi::encode(&[
+                Token::Uint(10.into()),
+                Token::Uint(20.into()),
+                Token::Uint(30.into()),
+            ])),
+        ]),
+        Token::Tuple(vec![
+            Token::Bool(true),
+            Token::Bytes(ethabi::encode(&[Token::Uint(1_000.into())])),
+        ]),
+        Token::Tuple(vec![
+            Token::Bool(true),
+            Token::Bytes(ethabi::encode(&[
+                Token::Uint(U256::one() << 96),
+                Token::Uint(42.into()),
+            ])),
+        ]),
+        Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+    ])]);
+
+    let (reserves, balance, slot0, failed): (
+        CallResult<UniswapV2Reserves>,
+        CallResult<U256>,
+        CallResult<Slot0>,
+        CallResult<u8>,
+    ) = Aggregate3Batch::decode_aggregate3(&input).expect("it decodes");
+    assert_eq!(
+        reserves,
+        Ok(Wrapped(UniswapV2Reserves {
+            r0: 10,
+            r1: 20,
+            timestamp: 30
+        }))
+    );
+    assert_eq!(balance, Ok(Wrapped(U256::from(1_000))));
+    assert_eq!(
+        slot0,
+        Ok(Wrapped(Slot0 {
+            sqrt_price_x96: U256::one() << 96,
+            tick: 42
+        }))
+    );
+    assert!(matches!(failed, Err(CallError::Reverted(_))));
+
+    // the tuple must match the batch length
+    let short: Result<(CallResult<UniswapV2Reserves>, CallResult<U256>), ()> =
+        Aggregate3Batch::decode_aggregate3(&input);
+    assert!(short.is_err());
+}
@@ -0,0 +1,142 @@
+#![cfg(test)]
+
+use ethabi_static::{
+    create2_address, keccak256, AddressZcp, BytesZcp, DecodePacked, EncodePacked, V3Hop, V3Path,
+};
+use ethereum_types::{H160, U256};
+use hex_literal::hex;
+
+const DAI: [u8; 20] = hex!("da10009cbd5d07dd0cecc66161fc93d7c9000da1");
+const USDT: [u8; 20] = hex!("fd086bc7cd5c481dcc9c85ebe478a1c0b69fcbb9");
+const WETH: [u8; 20] = hex!("82af49447d8a07e3bd95bd0d56f35241523fbab1");
+
+// the `path` from the `as_tuple` transform data
+const PATH: [u8; 66] = hex!("da10009cbd5d07dd0cecc66161fc93d7c9000da1000064fd086bc7cd5c481dcc9c85ebe478a1c0b69fcbb90001f482af49447d8a07e3bd95bd0d56f35241523fbab1");
+
+#[test]
+fn v3_path_hops() {
+    let path = V3Path::decode_packed(&PATH).expect("it decodes");
+    assert_eq!(path.len(), 2);
+    assert_eq!(path.token_in(), AddressZcp(&DAI));
+    assert_eq!(path.token_out(), AddressZcp(&WETH));
+    assert_eq!(
+        path.hops().collect::<Vec<_>>(),
+        vec![
+            V3Hop {
+                token_in: AddressZcp(&DAI),
+                fee: 100,
+                token_out: AddressZcp(&USDT),
+            },
+            V3Hop {
+                token_in: AddressZcp(&USDT),
+                fee: 500,
+                token_out: AddressZcp(&WETH),
+            },
+        ]
+    );
+
+    assert!(V3Path::new(&PATH[..20]).is_err());
+    assert!(V3Path::new(&PATH[..65]).is_err());
+    assert_eq!(V3Path::new(&PATH[..43]).unwrap().hops().len(), 1);
+}
+
+#[test]
+fn derived_packed_round_trip() {
+    #[derive(Debug, PartialEq, EncodePacked, DecodePacked)]
+    struct Hop<'a> {
+        token_in: AddressZcp<'a>,
+        #[ethabi(abi_type = "uint24")]
+        fee: u32,
+        #[ethabi(rust_only)]
+        visited: bool,
+        rest: BytesZcp<'a>,
+    }
+
+    let hop = Hop::decode_packed(&PATH).expect("it decodes");
+    assert_eq!(
+        hop,
+        Hop {
+            token_in: AddressZcp(&DAI),
+            fee: 100,
+            visited: false,
+            rest: BytesZcp(&PATH[23..]),
+        }
+    );
+    assert_eq!(hop.encode_packed(), PATH);
+
+    #[derive(Debug, PartialEq, EncodePacked, DecodePacked)]
+    struct Mixed {
+        flag: bool,
+        small: u16,
+        big: U256,
+        #[ethabi(ignore)]
+        ignored: u8,
+    }
+    let mixed = Mixed {
+        flag: true,
+        small: 0x0102,
+        big: U256::from(3),
+        ignored: 4,
+    };
+    let packed = mixed.encode_packed();
+    assert_eq!(packed.len(), 1 + 2 + 32 + 1);
+    assert_eq!(&packed[..3], &hex!("010102"));
+    assert_eq!(
+        Mixed::decode_packed(&packed).unwrap(),
+        Mixed {
+            ignored: 0,
+            ..mixed
+        }
+    );
+
+    // trailing bytes
+    assert!(Mixed::decode_packed(&[packed.as_slice(), &[0]].concat()).is_err());
+    // too short
+    assert!(Mixed::decode_packed(&packed[1..]).is_err());
+}
+
+#[test]
+fn packed_uint_wider_than_its_type() {
+    // an alias hides the Rust type from the derive
+    type Fee = u32;
+
+    #[derive(Debug, PartialEq, EncodePacked, DecodePacked)]
+    struct Wide {
+        #[ethabi(abi_type = "uint64")]
+        fee: Fee,
+    }
+
+    let packed = Wide { fee: 0x0102_0304 }.encode_packed();
+    assert_eq!(packed, hex!("0000000001020304"));
+    assert_eq!(Wide::decode_packed(&packed), Ok(Wide { fee: 0x0102_0304 }));
+    // too big for a `u32`
+    assert!(Wide::decode_packed(&hex!("0000000101020304")).is_err());
+}
+
+#[test]
+fn create2_uniswap_v2_pair() {
+    #[derive(EncodePacked)]
+    struct PairSalt<'a> {
+        token0: AddressZcp<'a>,
+        token1: AddressZcp<'a>,
+    }
+
+    let usdc = hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    let weth = hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+    let salt = keccak256(
+        &PairSalt {
+            token0: AddressZcp(&usdc),
+            token1: AddressZcp(&weth),
+        }
+        .encode_packed(),
+    );
+
+    assert_eq!(
+        create2_address(
+            &hex!("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"),
+            &salt,
+            &hex!("96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f"),
+        ),
+        H160(hex!("b4e16d0168e52d35cacd2c6185b44281ec28c9dc"))
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use ethabi::Token;
+use ethabi_static::{
+    AddressZcp, Array, BytesZcp, DecodeStatic, FixedArrayZcp, FixedBytesZcp, Tuple, Tuples, Wrapped,
+};
+use ethereum_types::{H160, U256};
+use hex_literal::hex;
+use serde::Serialize;
+
+#[test]
+fn address_is_checksummed() {
+    // EIP-55 test vectors
+    for checksummed in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let address: H160 = checksummed.parse().unwrap();
+        assert_eq!(
+            serde_json::to_value(AddressZcp(address.as_fixed_bytes())).unwrap(),
+            checksummed,
+        );
+    }
+}
+
+#[test]
+fn derived_struct_serializes() {
+    #[derive(Debug, DecodeStatic, Serialize)]
+    struct Inner<'a> {
+        id: u32,
+        tag: FixedBytesZcp<'a, 4>,
+        data: BytesZcp<'a>,
+    }
+
+    #[derive(Debug, DecodeStatic, Serialize)]
+    struct Outer<'a> {
+        owner: AddressZcp<'a>,
+        amount: U256,
+        data: BytesZcp<'a>,
+        inner: Tuple<Inner<'a>>,
+        items: Tuples<Inner<'a>>,
+    }
+
+    let inner = |id: u32, tag: [u8; 4], data: &[u8]| {
+        Token::Tuple(vec![
+            Token::Uint(id.into()),
+            Token::FixedBytes(tag.to_vec()),
+            Token::Bytes(data.to_vec()),
+        ])
+    };
+    let input = ethabi::encode(&[
+        Token::Address(H160(hex!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"))),
+        Token::Uint(U256::from(255)),
+        Token::Bytes(hex!("c0ffee").to_vec()),
+        inner(1, hex!("deadbeef"), &[]),
+        Token::Array(vec![
+            inner(2, hex!("00000001"), &hex!("01")),
+            inner(3, hex!("00000002"), &hex!("0202")),
+        ]),
+    ]);
+
+    let outer = Outer::decode(&input).expect("it decodes");
+    assert_eq!(
+        serde_json::to_value(&outer).unwrap(),
+        serde_json::json!({
+            "owner": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "amount": "0xff",
+            "data": "0xc0ffee",
+            "inner": { "id": 1, "tag": "0xdeadbeef", "data": "0x" },
+            "items": [
+                { "id": 2, "tag": "0x00000001", "data": "0x01" },
+                { "id": 3, "tag": "0x00000002", "data": "0x0202" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn helpers_serialize_transparently() {
+    let address = hex!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+    assert_eq!(
+        serde_json::to_value(Wrapped(AddressZcp(&address))).unwrap(),
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+    assert_eq!(
+        serde_json::to_value(Array(vec![1_u8, 2, 3])).unwrap(),
+        serde_json::json!([1, 2, 3])
+    );
+    assert_eq!(
+        serde_json::to_value(FixedArrayZcp(&[true, false])).unwrap(),
+        serde_json::json!([true, false])
+    );
+}
@@ -0,0 +1,79 @@
+#![cfg(test)]
+
+use arrayvec::ArrayVec;
+use ethabi::Token;
+use ethabi_static::{AbiType, AddressZcp, BytesZcp, DecodeStatic, StrZcp};
+use ethereum_types::H160;
+use smallvec::SmallVec;
+
+#[derive(Debug, PartialEq, DecodeStatic)]
+struct Swap<'a> {
+    path: SmallVec<[AddressZcp<'a>; 4]>,
+    fills: ArrayVec<u64, 2>,
+    hooks: SmallVec<[BytesZcp<'a>; 2]>,
+    memo: StrZcp<'a>,
+}
+
+fn swap(hops: u8, fills: &[u64]) -> Vec<u8> {
+    ethabi::encode(&[
+        Token::Array((0..hops).map(|i| Token::Address(H160([i; 20]))).collect()),
+        Token::Array(fills.iter().map(|f| Token::Uint((*f).into())).collect()),
+        Token::Array(vec![Token::Bytes(vec![1; 33]), Token::Bytes(vec![])]),
+        Token::String("exact in".into()),
+    ])
+}
+
+#[test]
+fn inline_array_fields() {
+    assert_eq!(Swap::abi_type(), "(address[],uint64[],bytes[],string)");
+
+    let input = swap(3, &[100, 200]);
+    let decoded = Swap::decode(&input).unwrap();
+    assert_eq!(decoded, Swap::decode_strict(&input).unwrap());
+    assert_eq!(decoded.path.len(), 3);
+    assert!(!decoded.path.spilled());
+    assert_eq!(decoded.path[2], AddressZcp(&[2; 20]));
+    assert_eq!(decoded.fills.as_slice(), &[100, 200]);
+    assert_eq!(decoded.hooks[0], BytesZcp(&[1; 33]));
+    assert_eq!(decoded.memo, StrZcp("exact in"));
+
+    // a longer path spills to the heap
+    let input = swap(6, &[]);
+    let decoded = Swap::decode_strict(&input).unwrap();
+    assert!(decoded.path.spilled());
+    assert_eq!(decoded.path[5], AddressZcp(&[5; 20]));
+    assert!(decoded.fills.is_empty());
+}
+
+#[test]
+fn array_vec_over_capacity() {
+    let input = swap(2, &[1, 2, 3]);
+    assert!(Swap::decode(&input).is_err());
+    assert!(Swap::decode_strict(&input).is_err());
+    assert!(
+        ArrayVec::<u64, 2>::decode(&ethabi::encode(&[Token::Array(vec![
+            Token::Uint(1.into());
+            3
+        ])]))
+        .is_err()
+    );
+
+    // within capacity after decoding into a full one
+    let (full, short) = (swap(2, &[1, 2]), swap(5, &[3]));
+    let mut decoded = Swap::decode(&full).unwrap();
+    decoded.decode_into(&short).unwrap();
+    assert_eq!(decoded.fills.as_slice(), &[3]);
+    assert_eq!(decoded.path.len(), 5);
+}
+
+#[test]
+fn hostile_lengths_are_errors() {
+    // a length word far past the payload, one element follows
+    let mut input = ethabi::encode(&[Token::Array(vec![Token::Uint(7.into())])]);
+    input[62] = 0xff;
+    input[63] = 0xff;
+    assert!(SmallVec::<[u64; 2]>::decode(&input).is_err());
+    assert!(SmallVec::<[u64; 2]>::decode_strict(&input).is_err());
+    assert!(ArrayVec::<u64, 2>::decode(&input).is_err());
+    assert!(ArrayVec::<u64, 2>::decode_strict(&input).is_err());
+}
@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use ethabi::Token;
+use ethabi_static::{AddressZcp, BytesZcp, DecodeStatic, FixedBytesZcp, Tuple, Tuples};
+use ethereum_types::{H160, U256};
+
+#[derive(Debug, PartialEq, DecodeStatic)]
+struct Order<'a> {
+    maker: AddressZcp<'a>,
+    amount: u64,
+    partial: bool,
+    salt: FixedBytesZcp<'a, 4>,
+    signature: BytesZcp<'a>,
+    hooks: Vec<BytesZcp<'a>>,
+}
+
+fn encoded_order() -> Vec<u8> {
+    ethabi::encode(&[
+        Token::Address(H160([0x11; 20])),
+        Token::Uint(U256::from(1_000)),
+        Token::Bool(true),
+        Token::FixedBytes(vec![1, 2, 3, 4]),
+        Token::Bytes(vec![0xaa; 65]),
+        Token::Array(vec![Token::Bytes(vec![0xbb; 3]), Token::Bytes(vec![])]),
+    ])
+}
+
+/// Set the word at `slot` to `value`
+fn set_word(buf: &mut [u8], slot: usize, value: usize) {
+    buf[slot * 32..slot * 32 + 32].copy_from_slice(&ethabi::encode(&[Token::Uint(value.into())]));
+}
+
+#[test]
+fn canonical_encoding_decodes() {
+    let input = encoded_order();
+    let order = Order::decode_strict(&input).expect("it decodes");
+    assert_eq!(order, Order::decode(&input).unwrap());
+    assert_eq!(order.signature.len(), 65);
+    assert_eq!(order.hooks.len(), 2);
+}
+
+#[test]
+fn dirty_values_are_rejected() {
+    let input = encoded_order();
+    for (idx, value) in [
+        (0, 1),                // address high bytes
+        (32 + 5, 1),           // uint64 high bytes
+        (64 + 31, 2),          // bool other than 0/1
+        (96 + 4, 1),           // bytes4 right padding
+        (6 * 32 + 32 + 65, 1), // bytes padding after the signature
+    ] {
+        let mut dirty = input.clone();
+        dirty[idx] = value;
+        assert!(Order::decode(&dirty).is_ok());
+        assert!(Order::decode_strict(&dirty).is_err(), "byte {idx} accepted");
+    }
+}
+
+#[test]
+fn misordered_offsets_are_rejected() {
+    let input = encoded_order();
+    let signature = 6 * 32;
+    let hooks = signature + 32 + 96;
+
+    // tails swapped
+    let mut swapped = input.clone();
+    set_word(&mut swapped, 4, hooks);
+    set_word(&mut swapped, 5, signature);
+    assert!(Order::decode_strict(&swapped).is_err());
+
+    // the hooks tail starts inside the signature
+    let mut overlapping = input.clone();
+    set_word(&mut overlapping, 5, signature + 32);
+    assert!(Order::decode_strict(&overlapping).is_err());
+
+    // pointing back into the heads
+    let mut into_heads = input.clone();
+    set_word(&mut into_heads, 4, 32);
+    assert!(Order::decode_strict(&into_heads).is_err());
+
+    // unaligned
+    let mut unaligned = input;
+    set_word(&mut unaligned, 4, signature + 1);
+    assert!(Order::decode_strict(&unaligned).is_err());
+}
+
+#[test]
+fn top_level_offset_into_its_head_is_rejected() {
+    // the offset word 0 points at itself, read as an empty array
+    let input = [0u8; 64];
+    assert!(Vec::<U256>::decode(&input).is_ok());
+    assert!(Vec::<U256>::decode_strict(&input).is_err());
+    assert!(BytesZcp::decode_strict(&input).is_err());
+
+    let mut canonical = input;
+    canonical[31] = 32;
+    assert_eq!(Vec::<U256>::decode_strict(&canonical), Ok(vec![]));
+}
+
+#[test]
+fn strict_tuples() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Call<'a> {
+        target: AddressZcp<'a>,
+        data: BytesZcp<'a>,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Calls<'a> {
+        calls: Tuples<Call<'a>>,
+    }
+
+    let call =
+        |data: Vec<u8>| Token::Tuple(vec![Token::Address(H160([0x22; 20])), Token::Bytes(data)]);
+    let input = ethabi::encode(&[Token::Array(vec![call(vec![1]), call(vec![2, 2])])]);
+    let calls = Calls::decode_strict(&input).expect("it decodes");
+    assert_eq!(calls, Calls::decode(&input).unwrap());
+
+    // both elements at the same offset
+    let mut repeated = input.clone();
+    repeated[3 * 32..4 * 32].copy_from_slice(&input[2 * 32..3 * 32]);
+    assert!(Calls::decode(&repeated).is_ok());
+    assert!(Calls::decode_strict(&repeated).is_err());
+}
+
+#[test]
+fn aliased_nested_tails_are_rejected() {
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Inner<'a> {
+        x: BytesZcp<'a>,
+    }
+    #[derive(Debug, PartialEq, DecodeStatic)]
+    struct Outer<'a> {
+        a: Tuple<Inner<'a>>,
+        b: BytesZcp<'a>,
+    }
+
+    let input = ethabi::encode(&[
+        Token::Tuple(vec![Token::Bytes(vec![1, 2])]),
+        Token::Bytes(vec![3]),
+    ]);
+    assert!(Outer::decode_strict(&input).is_ok());
+
+    // `b` is `a.x`
+    let mut aliased = input;
+    set_word(&mut aliased, 1, 3 * 32);
+    let outer = Outer::decode(&aliased).unwrap();
+    assert_eq!(outer.b, outer.a.0.x);
+    assert!(Outer::decode_strict(&aliased).is_err());
+
+    // the second element starts inside the first, at `x`'s length then its zeroed payload
+    let inner = |x: Vec<u8>| Token::Tuple(vec![Token::Bytes(x)]);
+    let elements = vec![inner(vec![0; 32]), inner(vec![])];
+    let input = ethabi::encode(&[Token::Array(elements.clone())]);
+    assert!(<Vec<Inner>>::decode_strict(&input).is_ok());
+    let mut aliased = input;
+    set_word(&mut aliased, 3, 3 * 32);
+    assert_eq!(<Vec<Inner>>::decode(&aliased).unwrap()[1].x, BytesZcp(&[]));
+    assert!(<Vec<Inner>>::decode_strict(&aliased).is_err());
+
+    let input = ethabi::encode(&[Token::FixedArray(elements)]);
+    assert!(<[Inner; 2]>::decode_strict(&input).is_ok());
+    let mut aliased = input;
+    set_word(&mut aliased, 2, 3 * 32);
+    assert_eq!(<[Inner; 2]>::decode(&aliased).unwrap()[1].x, BytesZcp(&[]));
+    assert!(<[Inner; 2]>::decode_strict(&aliased).is_err());
+}
@@ -0,0 +1,7 @@
+#![cfg(test)]
+
+#[test]
+fn derive_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("ui/*.rs");
+}
@@ -0,0 +1,73 @@
+#![cfg(test)]
+
+use std::collections::HashSet;
+
+use ethabi::Token;
+use ethabi_static::{
+    AbiType, Array, BytesZcp, DecodeStatic, FixedArrayZcp, FixedBytesZcp, Tuples, Word,
+};
+use ethereum_types::U256;
+use hex_literal::hex;
+
+#[test]
+fn fixed_array_borrows_words() {
+    let input = ethabi::encode(&[
+        Token::Uint(1.into()),
+        Token::FixedArray(vec![
+            Token::FixedBytes(hex!("aa").repeat(32)),
+            Token::FixedBytes(hex!("bb").repeat(32)),
+            Token::FixedBytes(hex!("cc").repeat(32)),
+        ]),
+    ]);
+
+    let array = FixedArrayZcp::<3, Word>::decode_static(&input, 32).expect("it decodes");
+    assert_eq!(array[1], Word([0xbb; 32]));
+    assert_eq!(array.len(), 3);
+    // no copies, the words point into the input
+    assert_eq!(array.0.as_ptr() as *const u8, input[32..].as_ptr());
+    assert_eq!(
+        array.into_iter().map(|w| w.0[0]).collect::<Vec<_>>(),
+        [0xaa, 0xbb, 0xcc]
+    );
+    assert_eq!(<FixedArrayZcp<3, Word>>::abi_type(), "bytes32[3]");
+    assert_eq!(
+        U256::from(Word(input[..32].try_into().unwrap())),
+        U256::one()
+    );
+
+    // nested `bytes32[2][2]` is laid out inline too
+    let nested = FixedArrayZcp::<2, [Word; 2]>::decode(&input[..128]).expect("it decodes");
+    assert_eq!(nested[1][1], Word([0xcc; 32]));
+
+    // too short
+    assert!(FixedArrayZcp::<4, Word>::decode_static(&input, 32).is_err());
+}
+
+#[test]
+fn common_trait_impls() {
+    let raw = hex!("deadbeef");
+    let bytes = BytesZcp(&raw);
+    let copied = bytes;
+    assert_eq!(bytes, copied);
+    assert_eq!(bytes[1], 0xad);
+    assert_eq!(bytes.into_iter().count(), 4);
+
+    let fixed = FixedBytesZcp::<4>(&raw);
+    assert_eq!(&fixed[..2], &hex!("dead"));
+    assert_eq!(FixedBytesZcp::<4>::default().0, &[0; 4]);
+    let set: HashSet<_> = [fixed, fixed, FixedBytesZcp::default()]
+        .into_iter()
+        .collect();
+    assert_eq!(set.len(), 2);
+
+    let mut array = Array::<u8>::default();
+    array.push(1);
+    array.push(2);
+    assert_eq!(array[1], 2);
+    assert_eq!(array.clone(), array);
+    assert_eq!((&array).into_iter().sum::<u8>(), 3);
+
+    let tuples = Tuples(vec!["a", "b"]);
+    assert_eq!(&tuples[..], &["a", "b"]);
+    assert_eq!(tuples.into_iter().collect::<String>(), "ab");
+}
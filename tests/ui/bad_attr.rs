@@ -0,0 +1,23 @@
+use ethabi_static::DecodeStatic;
+
+fn doubled(buf: &[u8], offset: usize) -> Result<u64, ()> {
+    Ok(2 * u64::decode_static(buf, offset)?)
+}
+
+#[derive(DecodeStatic)]
+struct Unquoted {
+    #[ethabi(with = doubled)]
+    value: u64,
+}
+
+#[derive(DecodeStatic)]
+struct Misspelt {
+    #[ethabi(skp)]
+    value: u64,
+}
+
+#[derive(DecodeStatic)]
+#[ethabi(transparant)]
+struct Container(u64);
+
+fn main() {}
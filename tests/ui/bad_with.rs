@@ -0,0 +1,9 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+struct Custom {
+    #[ethabi(with = "not a path")]
+    a: u64,
+}
+
+fn main() {}
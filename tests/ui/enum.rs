@@ -0,0 +1,9 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+enum Side {
+    Buy,
+    Sell(u8),
+}
+
+fn main() {}
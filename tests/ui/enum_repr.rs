@@ -0,0 +1,10 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+#[repr(u16)]
+enum Operation {
+    Call,
+    DelegateCall,
+}
+
+fn main() {}
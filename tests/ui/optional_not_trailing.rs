@@ -0,0 +1,10 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+struct Slot0 {
+    #[ethabi(optional)]
+    fee_protocol: Option<u8>,
+    tick: u32,
+}
+
+fn main() {}
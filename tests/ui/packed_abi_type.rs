@@ -0,0 +1,15 @@
+use ethabi_static::{DecodePacked, EncodePacked};
+
+#[derive(EncodePacked)]
+struct TooWide {
+    #[ethabi(abi_type = "uint64")]
+    fee: u32,
+}
+
+#[derive(DecodePacked)]
+struct NotAUint {
+    #[ethabi(abi_type = "bytes4")]
+    selector: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+#[ethabi(transparent)]
+struct Reserves {
+    r0: u128,
+    r1: u128,
+}
+
+fn main() {}
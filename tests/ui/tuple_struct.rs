@@ -0,0 +1,7 @@
+use ethabi_static::DecodeStatic;
+use ethereum_types::U256;
+
+#[derive(DecodeStatic)]
+struct Reserves(U256, U256);
+
+fn main() {}
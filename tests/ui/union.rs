@@ -0,0 +1,9 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+union Word {
+    a: u64,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use ethabi_static::DecodeStatic;
+
+#[derive(DecodeStatic)]
+struct Empty;
+
+fn main() {}